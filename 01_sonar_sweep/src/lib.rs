@@ -1,12 +1,46 @@
+use common::{parse_at, Answer, Day, ParseError, Solution};
 
+pub const DAY: Day = Day::new::<Solver>(1, "Sonar Sweep", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_depths(input)
+    }
+
+    fn part_one(depths: &Self::Input) -> Answer {
+        let increase_count = depths.iter()
+            .zip(depths.iter().skip(1))
+            .filter(|(a, b)| a < b)
+            .count();
+        increase_count.into()
+    }
+
+    fn part_two(depths: &Self::Input) -> Answer {
+        let increase_count = depths
+            .windows(3)
+            .zip(depths.windows(3).skip(1))
+            .filter(|(a, b)| a.iter().sum::<u32>() < b.iter().sum::<u32>())
+            .count();
+        increase_count.into()
+    }
+}
+
+fn parse_depths(input: &str) -> Result<Vec<u32>, ParseError> {
+    input.lines().map(|l| parse_at(input, l)).collect()
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let depths = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(7), Solver::part_one(&depths));
+        assert_eq!(Answer::Number(5), Solver::part_two(&depths));
     }
 }
@@ -1,3 +1,46 @@
+use common::{parse_at, Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(2, "Dive!", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let commands: Vec<_> = parse_commands(input).collect::<Result<_, _>>()?;
+        if commands.is_empty() {
+            return Err(ParseError::new(1, 1, "there are no commands"));
+        }
+        Ok(commands)
+    }
+
+    fn part_one(commands: &Self::Input) -> Answer {
+        let sub_position = commands.iter().copied()
+            .map(Position::from)
+            .reduce(|a, b| a + b).unwrap();
+        (sub_position.x * sub_position.z).into()
+    }
+
+    fn part_two(commands: &Self::Input) -> Answer {
+        let mut submarine = Submarine::new();
+        commands.iter().for_each(|command| submarine.command(*command));
+        (submarine.position.x * submarine.position.z).into()
+    }
+}
+
+fn parse_commands(input: &str) -> impl Iterator<Item = Result<Command, ParseError>> + '_ {
+    input.lines().map(|line| {
+        let mut split = line.split_whitespace();
+        let (direction, magnitude) = match (split.next(), split.next()) {
+            (Some(direction), Some(magnitude)) => (direction, magnitude),
+            _ => return Err(ParseError::at(input, line, "expected a direction and a magnitude")),
+        };
+        let magnitude = parse_at::<i32>(input, magnitude)?;
+        Command::from(direction, magnitude).map_err(|e| e.within(input, direction))
+    })
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Position {
     pub x: i32,
@@ -29,6 +72,7 @@ impl std::ops::Add for Position {
     }
 }
 
+#[derive(Debug, Copy, Clone)]
 pub enum Command {
     Forward(i32),
     Up(i32),
@@ -36,12 +80,12 @@ pub enum Command {
 }
 
 impl Command {
-    pub fn from(direction: &str, magnitude: i32) -> Command {
+    pub fn from(direction: &str, magnitude: i32) -> Result<Command, ParseError> {
         match direction {
-            "forward" => Command::Forward(magnitude),
-            "up" => Command::Up(magnitude),
-            "down" => Command::Down(magnitude),
-            _ => panic!("cannot parse direction {} to command", direction)
+            "forward" => Ok(Command::Forward(magnitude)),
+            "up" => Ok(Command::Up(magnitude)),
+            "down" => Ok(Command::Down(magnitude)),
+            _ => Err(ParseError::new(1, 1, format!("cannot parse direction `{}` to command", direction))),
         }
     }
 }
@@ -51,6 +95,12 @@ pub struct Submarine {
     aim: i32,
 }
 
+impl Default for Submarine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Submarine {
     pub fn new() -> Submarine {
         Submarine {
@@ -85,4 +135,11 @@ mod tests {
         let result = start + add;
         assert_eq!(Position::new(3, 4), result);
     }
+
+    #[test]
+    fn test_no_commands() {
+        let error = Solver::parse("").err().unwrap();
+
+        assert_eq!("line 1, column 1: there are no commands", error.to_string());
+    }
 }
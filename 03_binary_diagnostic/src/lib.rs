@@ -1,36 +1,70 @@
-use std::collections::{HashSet,HashMap};
-use std::hash::Hash;
+use std::collections::HashSet;
+use common::{Answer, Counter, Day, ParseError, Solution};
 
-pub struct Counter<T> 
-    where T: Eq + Hash
-{
-    counts: HashMap<T, u32>,
+pub const DAY: Day = Day::new::<Solver>(3, "Binary Diagnostic", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+/// The diagnostic report: every value, and how many bits each one has.
+pub struct Report {
+    pub bit_count: usize,
+    pub values: Vec<u16>,
 }
 
-impl<T> Counter<T> 
-    where T: Eq + Hash
-{
-    pub fn new() -> Counter<T> {
-        Counter {
-            counts: HashMap::new(),
+impl Solution for Solver {
+    type Input = Report;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let first = input.lines().next()
+            .ok_or_else(|| ParseError::new(1, 1, "the report is empty"))?;
+        let bit_count = first.len();
+        if !(1..=16).contains(&bit_count) {
+            return Err(ParseError::at(input, first, format!("values must have from 1 to 16 bits, found {}", bit_count)));
         }
+
+        let values = input.lines().map(|line| {
+            if let Some(i) = line.find(|c| c != '0' && c != '1') {
+                return Err(ParseError::at(input, &line[i..], "values may only contain `0` and `1`"));
+            }
+            if line.len() != bit_count {
+                return Err(ParseError::at(input, line, format!("expected {} bits, found {}", bit_count, line.len())));
+            }
+            Ok(u16::from_str_radix(line, 2).unwrap())
+        }).collect::<Result<_, _>>()?;
+
+        Ok(Report { bit_count, values })
     }
 
-    pub fn add(&mut self, item: T) {
-        let entry = self.counts.entry(item).or_insert(0);
-        *entry += 1;
+    fn part_one(report: &Self::Input) -> Answer {
+        let bit_count = report.bit_count;
+
+        // build gamma from the most common bit at each index
+        let gamma = (0..bit_count).rev().fold(0_u32, |gamma, i| {
+            let mut counter: Counter<_> = Counter::new();
+            report.values.iter().for_each(|value| counter.add((value >> i) & 1));
+            let bit = if counter.count(&0) > counter.count(&1) { 0 } else { 1 };
+            (gamma << 1) | bit
+        });
+
+        // epsilon is every other bit
+        let epsilon = (!gamma) & (u32::MAX >> (32 - bit_count));
+
+        (gamma * epsilon).into()
     }
 
-    pub fn count(&self, item: T) -> Option<u32> {
-        self.counts.get(&item).copied()
+    fn part_two(report: &Self::Input) -> Answer {
+        let oxygen = find_oxygen(&report.values, report.bit_count);
+        let co2 = find_co2(&report.values, report.bit_count);
+
+        (oxygen * co2).into()
     }
 }
 
-pub fn find_oxygen(vals: &Vec<u16>, bits: usize) -> u32 {
+pub fn find_oxygen(vals: &[u16], bits: usize) -> u32 {
     let mut remaining: HashSet<u16> = vals.iter().copied().collect();
 
     for i in (0..bits).rev() {
-        let mut counter = Counter::new();
+        let mut counter: Counter<_> = Counter::new();
         let mask = 1 << i;
         let (mut zeroes, mut ones) = (HashSet::new(), HashSet::new());
         for num in remaining {
@@ -43,7 +77,7 @@ pub fn find_oxygen(vals: &Vec<u16>, bits: usize) -> u32 {
             }
         }
 
-        if counter.count(1) >= counter.count(0) {
+        if counter.count(&1) >= counter.count(&0) {
             remaining = ones;
         } else {
             remaining = zeroes;
@@ -57,11 +91,11 @@ pub fn find_oxygen(vals: &Vec<u16>, bits: usize) -> u32 {
     panic!("no oxygen value found");
 }
 
-pub fn find_co2(vals: &Vec<u16>, bits: usize) -> u32 {
+pub fn find_co2(vals: &[u16], bits: usize) -> u32 {
     let mut remaining: HashSet<u16> = vals.iter().copied().collect();
 
     for i in (0..bits).rev() {
-        let mut counter = Counter::new();
+        let mut counter: Counter<_> = Counter::new();
         let mask = 1 << i;
         let (mut zeroes, mut ones) = (HashSet::new(), HashSet::new());
         for num in remaining {
@@ -74,7 +108,7 @@ pub fn find_co2(vals: &Vec<u16>, bits: usize) -> u32 {
             }
         }
 
-        if counter.count(1) >= counter.count(0) {
+        if counter.count(&1) >= counter.count(&0) {
             remaining = zeroes;
         } else {
             remaining = ones;
@@ -93,8 +127,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let report = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(198), Solver::part_one(&report));
+        assert_eq!(Answer::Number(230), Solver::part_two(&report));
     }
 }
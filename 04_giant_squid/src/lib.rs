@@ -1,8 +1,100 @@
 use std::collections::HashMap;
+use common::{parse_at, Answer, Day, ParseError, Solution};
 
+pub const DAY: Day = Day::new::<Solver>(4, "Giant Squid", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    // the numbers to mark in order, and the boards to mark them on
+    type Input = (Vec<u32>, Vec<BingoBoard>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_bingo(input)
+    }
+
+    fn part_one((to_mark, boards): &Self::Input) -> Answer {
+        let mut boards = boards.clone();
+
+        for &mark in to_mark {
+            for board in boards.iter_mut() {
+                if MarkResult::Bingo == board.mark(mark) {
+                    let unmarked_sum: u32 = board.unmarked().iter().sum();
+                    return (unmarked_sum * mark).into();
+                }
+            }
+        }
+
+        panic!("no board reached bingo");
+    }
+
+    fn part_two((to_mark, boards): &Self::Input) -> Answer {
+        let mut boards = boards.clone();
+
+        let mut last_bingo_unmarked = None;
+        let mut last_bingo_num = None;
+
+        for &mark in to_mark {
+            for board in boards.iter_mut() {
+                if !board.bingo() && MarkResult::Bingo == board.mark(mark) {
+                    last_bingo_unmarked = Some(board.unmarked());
+                    last_bingo_num = Some(mark);
+                }
+            }
+        }
+
+        let unmarked_sum: u32 = last_bingo_unmarked.unwrap().iter().sum();
+        let result: u32 = unmarked_sum * last_bingo_num.unwrap();
+        result.into()
+    }
+}
+
+fn parse_bingo(input: &str) -> Result<(Vec<u32>, Vec<BingoBoard>), ParseError> {
+    let mut lines = input.lines();
+
+    let first = lines.next()
+        .ok_or_else(|| ParseError::new(1, 1, "expected a line of numbers to draw"))?;
+    let to_mark = first.split(',')
+        .map(|num| parse_at(input, num))
+        .collect::<Result<_, _>>()?;
+
+    // boards are separated by blank lines
+    let mut boards = Vec::new();
+    let mut board_lines = Vec::new();
+    for line in lines.chain([""]) {
+        if !line.trim().is_empty() {
+            board_lines.push(line);
+        } else if !board_lines.is_empty() {
+            boards.push(parse_board(input, &board_lines)?);
+            board_lines.clear();
+        }
+    }
+    if boards.is_empty() {
+        return Err(ParseError::at(input, &input[input.len()..], "there are no boards"));
+    }
+
+    Ok((to_mark, boards))
+}
+
+fn parse_board(input: &str, lines: &[&str]) -> Result<BingoBoard, ParseError> {
+    let mut nums = Vec::new();
+
+    for line in lines {
+        let row: Vec<u32> = line
+            .split_whitespace()
+            .map(|num| parse_at(input, num))
+            .collect::<Result<_, _>>()?;
+        if row.len() != BOARD_SIDE {
+            return Err(ParseError::at(input, line, format!("expected {} numbers in a board row, found {}", BOARD_SIDE, row.len())));
+        }
+        nums.extend(row);
+    }
+
+    BingoBoard::new(&nums).map_err(|e| e.within(input, lines[0]))
+}
 const BOARD_SIDE: usize = 5;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Coord {
     r: usize,
     c: usize,
@@ -25,7 +117,7 @@ impl Coord {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BingoBoard {
     coords: HashMap<u32, Coord>,
     marks: [bool; BOARD_SIDE.pow(2)],
@@ -33,13 +125,15 @@ pub struct BingoBoard {
 }
 
 impl BingoBoard {
-    pub fn new(board: &Vec<u32>) -> BingoBoard {
-        assert_eq!(BOARD_SIDE.pow(2), board.len());
+    pub fn new(board: &[u32]) -> Result<BingoBoard, ParseError> {
+        if board.len() != BOARD_SIDE.pow(2) {
+            return Err(ParseError::new(1, 1, format!("a board needs {} numbers, found {}", BOARD_SIDE.pow(2), board.len())));
+        }
 
         let coords = board.iter().enumerate().map(|(i, v)| (*v, Coord::from_index(i, 5))).collect();
         let marks = [false; BOARD_SIDE.pow(2)];
 
-        BingoBoard { coords, marks, bingo: false, }
+        Ok(BingoBoard { coords, marks, bingo: false, })
     }
 
     pub fn mark(&mut self, num: u32) -> MarkResult {
@@ -110,7 +204,7 @@ impl BingoBoard {
         //     if diag_bingo { return true; }
         // }
 
-        return false;
+        false
     }
 }
 
@@ -134,7 +228,7 @@ mod tests {
             21, 22, 23, 24, 25,
         ];
 
-        let mut board = BingoBoard::new(&board);
+        let mut board = BingoBoard::new(&board).unwrap();
         for num in [11, 12, 13, 14] {
             assert_eq!(MarkResult::Continue, board.mark(num));
         }
@@ -152,7 +246,7 @@ mod tests {
             21, 22, 23, 24, 25,
         ];
 
-        let mut board = BingoBoard::new(&board);
+        let mut board = BingoBoard::new(&board).unwrap();
         for num in [5, 15, 20, 25] {
             assert_eq!(MarkResult::Continue, board.mark(num));
         }
@@ -170,7 +264,7 @@ mod tests {
             21, 22, 23, 24, 25,
         ];
 
-        let mut board = BingoBoard::new(&board);
+        let mut board = BingoBoard::new(&board).unwrap();
         for num in [21, 17, 13, 5] {
             assert_eq!(MarkResult::Continue, board.mark(num));
         }
@@ -188,11 +282,18 @@ mod tests {
              2,  0, 12,  3,  7,
         ];
 
-        let mut board = BingoBoard::new(&board);
+        let mut board = BingoBoard::new(&board).unwrap();
         for num in [7, 4, 9, 5, 11, 17, 23] {
             assert_eq!(MarkResult::Continue, board.mark(num));
         }
 
         assert_eq!(MarkResult::Continue, board.mark(2));
     }
+
+    #[test]
+    fn no_boards() {
+        let error = Solver::parse("7,4,9\n\n").err().unwrap();
+
+        assert_eq!("line 3, column 1: there are no boards", error.to_string());
+    }
 }
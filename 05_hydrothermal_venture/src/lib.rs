@@ -1,3 +1,54 @@
+use regex::Regex;
+use common::{parse_at, Answer, Counter, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(5, "Hydrothermal Venture", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<CompassLineSegment>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_segments(input).collect()
+    }
+
+    fn part_one(segments: &Self::Input) -> Answer {
+        // count only horizontal/vertical lines
+        overlap_count(segments.iter().filter(|segment| segment.is_up_down())).into()
+    }
+
+    fn part_two(segments: &Self::Input) -> Answer {
+        // count all lines
+        overlap_count(segments.iter()).into()
+    }
+}
+
+fn parse_segments(input: &str) -> impl Iterator<Item = Result<CompassLineSegment, ParseError>> + '_ {
+    let regex = Regex::new(r"[0-9]+").unwrap();
+    input.lines().map(move |line| {
+        let nums: Vec<i32> = regex.find_iter(line)
+            .map(|num| parse_at(input, num.as_str()))
+            .collect::<Result<_, _>>()?;
+        if nums.len() != 4 {
+            return Err(ParseError::at(input, line, format!("expected 4 numbers, found {}", nums.len())));
+        }
+        let (c1, c2) = (Coord::new(nums[0], nums[1]), Coord::new(nums[2], nums[3]));
+        CompassLineSegment::new(c1, c2)
+            .ok_or_else(|| ParseError::at(input, line, "line must be horizontal, vertical, or diagonal at 45 degrees"))
+    })
+}
+
+fn overlap_count<'a>(segments: impl Iterator<Item = &'a CompassLineSegment>) -> usize {
+    let mut counter = Counter::new();
+    for seg in segments {
+        for coord in seg.integer_coords() {
+            counter.add(coord);
+        }
+    }
+
+    counter.with_count_ge(2).len()
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Coord {
     x: i32,
@@ -48,11 +99,15 @@ pub struct CompassLineSegment {
 }
 
 impl CompassLineSegment {
-    pub fn new(c1: Coord, c2: Coord) -> CompassLineSegment {
-        assert_ne!(c1, c2);
+    // Returns `None` if the two ends are the same or do not lie on a compass
+    // line.
+    pub fn new(c1: Coord, c2: Coord) -> Option<CompassLineSegment> {
+        if c1 == c2 {
+            return None;
+        }
 
         let mut integer_coords = vec![c1];
-        let slope = Self::find_slope(&c1, &c2);
+        let slope = Self::find_slope(&c1, &c2)?;
         let delta = slope.delta();
         loop {
             let next = *integer_coords.last().unwrap() + delta;
@@ -62,7 +117,7 @@ impl CompassLineSegment {
             }
         }
 
-        CompassLineSegment{ slope, integer_coords }
+        Some(CompassLineSegment{ slope, integer_coords })
     }
 
     pub fn integer_coords(&self) -> Vec<Coord> {
@@ -74,43 +129,38 @@ impl CompassLineSegment {
     }
 
     pub fn is_up_down(&self) -> bool {
-        match self.slope {
-            Compass::N | Compass::S | Compass::E | Compass::W => true,
-            _ => false,
-        }
+        matches!(self.slope, Compass::N | Compass::S | Compass::E | Compass::W)
     }
 
     // Integer slope of line from c1 to c2
-    fn find_slope(c1: &Coord, c2: &Coord) -> Compass {
+    fn find_slope(c1: &Coord, c2: &Coord) -> Option<Compass> {
         if c1.x == c2.x {
             if c1.y > c2.y {
-                Compass::S
+                Some(Compass::S)
             } else {
-                Compass::N
+                Some(Compass::N)
             }
         } else if c1.y == c2.y {
             if c1.x > c2.x {
-                Compass::W
+                Some(Compass::W)
             } else {
-                Compass::E
+                Some(Compass::E)
             }
+        } else if (c2.y - c1.y).abs() != (c2.x - c1.x).abs() {
+            // not a compass line
+            None
         } else {
-            assert_eq!((c2.y - c1.y).abs(), (c2.x - c1.x).abs(), "line is not a compass line");
             let m = (c2.y - c1.y) / (c2.x - c1.x);
             if m == 1 {
                 if c1.y > c2.y {
-                    Compass::SW
-                } else {
-                    Compass::NE
-                }
-            } else if m == -1 {
-                if c1.y > c2.y {
-                    Compass::SE
+                    Some(Compass::SW)
                 } else {
-                    Compass::NW
+                    Some(Compass::NE)
                 }
+            } else if c1.y > c2.y {
+                Some(Compass::SE)
             } else {
-                panic!("slope is not a compass direction")
+                Some(Compass::NW)
             }
         }
     }
@@ -123,7 +173,7 @@ mod tests {
     #[test]
     fn line_north() {
         let (c1, c2) = (Coord::new(0, 0), Coord::new(0, 5));
-        let line = CompassLineSegment::new(c1, c2);
+        let line = CompassLineSegment::new(c1, c2).unwrap();
         let expected: Vec<Coord> = (0..=5).map(|v| Coord::new(0, v)).collect();
 
         assert_eq!(expected, line.integer_coords());
@@ -132,7 +182,7 @@ mod tests {
     #[test]
     fn line_west() {
         let (c1, c2) = (Coord::new(7, -7), Coord::new(-10, -7));
-        let line = CompassLineSegment::new(c1, c2);
+        let line = CompassLineSegment::new(c1, c2).unwrap();
         let expected: Vec<Coord> = (-10..=7).rev().map(|v| Coord::new(v, -7)).collect();
 
         assert_eq!(expected, line.integer_coords());
@@ -141,7 +191,7 @@ mod tests {
     #[test]
     fn line_southeast() {
         let (c1, c2) = (Coord::new(0, 0), Coord::new(3, -3));
-        let line = CompassLineSegment::new(c1, c2);
+        let line = CompassLineSegment::new(c1, c2).unwrap();
         let expected: Vec<Coord> = (0..=3).map(|v| Coord::new(v, -v)).collect();
 
         assert_eq!(expected, line.integer_coords());
@@ -1,7 +1,65 @@
+use common::{parse_at, Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(6, "Lanternfish", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_timers(input)
+    }
+
+    fn part_one(timers: &Self::Input) -> Answer {
+        let mut lanternfish: Vec<_> = timers.iter().copied()
+            .map(Lanternfish::from)
+            .collect();
+
+        for _ in 0..80 {
+            let mut new_fish: Vec<Lanternfish> = lanternfish
+                .iter_mut()
+                .filter_map(|fish| fish.step())
+                .collect();
+            lanternfish.append(&mut new_fish);
+        }
+
+        lanternfish.len().into()
+    }
+
+    fn part_two(timers: &Self::Input) -> Answer {
+        let mut lanternfish = LanternfishSchool::from(timers);
+
+        (0..256).for_each(|_| lanternfish.step());
+
+        lanternfish.size().into()
+    }
+}
+
+fn parse_timers(input: &str) -> Result<Vec<u8>, ParseError> {
+    let line = input.lines().next()
+        .ok_or_else(|| ParseError::new(1, 1, "expected a line of comma-separated timers"))?;
+    line.split(',')
+        .map(|num| {
+            let timer = parse_at(input, num)?;
+            if timer > 8 {
+                return Err(ParseError::at(input, num, "timers must be from 0 to 8"));
+            }
+            Ok(timer)
+        })
+        .collect()
+}
+
 pub struct Lanternfish {
     timer: u8,
 }
 
+impl Default for Lanternfish {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Lanternfish {
     pub fn new() -> Lanternfish {
         Lanternfish { timer: 8 }
@@ -68,8 +126,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let timers = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(5934), Solver::part_one(&timers));
+        assert_eq!(Answer::Number(26984457539), Solver::part_two(&timers));
     }
 }
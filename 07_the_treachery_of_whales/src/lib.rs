@@ -1,3 +1,45 @@
+use common::{parse_at, Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(7, "The Treachery of Whales", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_crabs(input)
+    }
+
+    fn part_one(crabs: &Self::Input) -> Answer {
+        let max_crab = *crabs.iter().max().unwrap();
+        let min_fuel: i32 = (0..=max_crab).map(|p| {
+            crabs.iter().map(|v| (v - p).abs()).sum()
+        }).min().unwrap();
+
+        min_fuel.into()
+    }
+
+    fn part_two(crabs: &Self::Input) -> Answer {
+        let max_crab = *crabs.iter().max().unwrap();
+        let min_fuel: i32 = (0..=max_crab).map(|p| {
+            crabs.iter().map(|v| {
+                let n = (v - p).abs();
+                (n * (n + 1)) / 2
+            }).sum()
+        }).min().unwrap();
+
+        min_fuel.into()
+    }
+}
+
+fn parse_crabs(input: &str) -> Result<Vec<i32>, ParseError> {
+    let line = input.lines().next()
+        .ok_or_else(|| ParseError::new(1, 1, "expected a line of comma-separated numbers"))?;
+    line.split(',')
+        .map(|num| parse_at(input, num))
+        .collect()
+}
 
 
 #[cfg(test)]
@@ -5,8 +47,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let crabs = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(37), Solver::part_one(&crabs));
+        assert_eq!(Answer::Number(168), Solver::part_two(&crabs));
     }
 }
@@ -1,17 +1,86 @@
 use std::collections::{HashMap, HashSet};
+use common::{Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(8, "Seven Segment Search", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+/// One line of notes: the ten unique signal patterns, and the four digits of
+/// the output value.
+pub struct Entry {
+    pub patterns: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl Solution for Solver {
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|line| {
+            let (patterns, outputs) = line.split_once('|')
+                .ok_or_else(|| ParseError::at(input, line, "expected patterns and outputs separated by `|`"))?;
+            let patterns = parse_digits(input, patterns, 10)?;
+            let outputs = parse_digits(input, outputs, 4)?;
+            Ok(Entry { patterns, outputs })
+        }).collect()
+    }
+
+    fn part_one(entries: &Self::Input) -> Answer {
+        // count 1, 4, 7, 8 in output
+        // count outputs with length 2, 3, 4, or 7
+        let unique_count: u32 = entries.iter()
+            .map(|entry| entry.outputs.iter().filter_map(|digit| {
+                match digit.len() {
+                    2 | 3 | 4 | 7 => Some(1),
+                    _ => None
+                }
+            }).sum::<u32>())
+            .sum();
+
+        unique_count.into()
+    }
+
+    fn part_two(entries: &Self::Input) -> Answer {
+        let result: u32 = entries.iter().map(|entry| {
+            let patterns: Vec<&str> = entry.patterns.iter().map(String::as_str).collect();
+
+            let decoder = SegmentDecoder::from(&patterns);
+            let result: u32 = entry.outputs.iter().rev()
+                .enumerate().map(|(i, out)| decoder.decode(out).unwrap() as u32 * 10_u32.pow(i as u32)).sum();
+
+            result
+        }).sum();
+
+        result.into()
+    }
+}
+
+// parse `count` whitespace-separated digits from `field`, a slice of `input`
+fn parse_digits(input: &str, field: &str, count: usize) -> Result<Vec<String>, ParseError> {
+    let digits: Vec<&str> = field.split_whitespace().collect();
+    if digits.len() != count {
+        return Err(ParseError::at(input, field, format!("expected {} digits, found {}", count, digits.len())));
+    }
+
+    digits.into_iter().map(|digit| {
+        match digit.find(|c: char| !('a'..='g').contains(&c)) {
+            Some(i) => Err(ParseError::at(input, &digit[i..], "segments must be from `a` to `g`")),
+            None => Ok(String::from(digit)),
+        }
+    }).collect()
+}
 
 pub struct SegmentDecoder {
-    nums_to_wires: HashMap<u8, UnknownDigit>,
     wires_to_nums: HashMap<String, u8>,
 }
 
 impl SegmentDecoder {
-    pub fn from(digits: &Vec<&str>) -> SegmentDecoder {
+    pub fn from(digits: &[&str]) -> SegmentDecoder {
         let mut seg_counts: HashMap<usize, HashSet<UnknownDigit>> = HashMap::new();
         digits.iter()
             .map(|d| UnknownDigit::new(d))
             .for_each(|digit| {
-                let entry = seg_counts.entry(digit.len()).or_insert(HashSet::new());
+                let entry = seg_counts.entry(digit.len()).or_default();
                 entry.insert(digit);
             });
         
@@ -26,19 +95,19 @@ impl SegmentDecoder {
         // oof...
 
         // "4" produces 2 segment overlaps with "2"
-        let two = seg_counts.get(&5).unwrap().iter().filter(|d| d.overlap(nums_to_wires.get(&4).unwrap().clone()) == 2).next().unwrap().clone();
+        let two = seg_counts.get(&5).unwrap().iter().find(|d| d.overlap(nums_to_wires.get(&4).unwrap().clone()) == 2).unwrap().clone();
         seg_counts.get_mut(&5).unwrap().remove(&two);
         // "1" produces 2 segment overlaps with "3"
-        let three = seg_counts.get(&5).unwrap().iter().filter(|d| d.overlap(nums_to_wires.get(&1).unwrap().clone()) == 2).next().unwrap().clone();
+        let three = seg_counts.get(&5).unwrap().iter().find(|d| d.overlap(nums_to_wires.get(&1).unwrap().clone()) == 2).unwrap().clone();
         seg_counts.get_mut(&5).unwrap().remove(&three);
         // "5" remains
         let five = seg_counts.get(&5).unwrap().iter().next().unwrap().clone();
 
         // "1" produces 1 segment overlap with "6"
-        let six = seg_counts.get(&6).unwrap().iter().filter(|d| d.overlap(nums_to_wires.get(&1).unwrap().clone()) == 1).next().unwrap().clone();
+        let six = seg_counts.get(&6).unwrap().iter().find(|d| d.overlap(nums_to_wires.get(&1).unwrap().clone()) == 1).unwrap().clone();
         seg_counts.get_mut(&6).unwrap().remove(&six);
         // "4" produces 4 segment overlaps with "9"
-        let nine = seg_counts.get(&6).unwrap().iter().filter(|d| d.overlap(nums_to_wires.get(&4).unwrap().clone()) == 4).next().unwrap().clone();
+        let nine = seg_counts.get(&6).unwrap().iter().find(|d| d.overlap(nums_to_wires.get(&4).unwrap().clone()) == 4).unwrap().clone();
         seg_counts.get_mut(&6).unwrap().remove(&nine);
         // "0" remains
         let zero = seg_counts.get(&6).unwrap().iter().next().unwrap().clone();
@@ -56,7 +125,7 @@ impl SegmentDecoder {
 
         let wires_to_nums = nums_to_wires.iter().map(|(k, v)| (v.chars.iter().collect(), *k)).collect();
 
-        SegmentDecoder { nums_to_wires, wires_to_nums }
+        SegmentDecoder { wires_to_nums }
     }
 
     pub fn decode(&self, digit: &str) -> Option<u8> {
@@ -96,8 +165,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let entries = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(26), Solver::part_one(&entries));
+        assert_eq!(Answer::Number(61229), Solver::part_two(&entries));
     }
 }
@@ -1,36 +1,57 @@
 use std::collections::{HashSet};
+use common::{Answer, Coord, Day, Grid, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(9, "Smoke Basin", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<Height>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_heights(input)
+    }
+
+    fn part_one(heights: &Self::Input) -> Answer {
+        let heightmap = Heightmap::new(heights);
+
+        heightmap.risk_level().into()
+    }
+
+    fn part_two(heights: &Self::Input) -> Answer {
+        let heightmap = Heightmap::new(heights);
+
+        let mut basins: Vec<usize> = heightmap.basins().iter().map(|b| b.len()).collect();
+        basins.sort();
+
+        let result: usize = basins.iter().copied().rev().take(3).reduce(|a, b| a * b).unwrap();
+
+        result.into()
+    }
+}
+
+fn parse_heights(input: &str) -> Result<Grid<Height>, ParseError> {
+    Grid::parse(input, |c| c.to_digit(10).ok_or_else(|| format!("`{}` is not a height", c)))
+}
 
 pub type Height = u32;
-type Coord = (usize, usize);
 
 type Basin = HashSet<Coord>;
 
 pub struct Heightmap {
-    map: Vec<Vec<Height>>,
-    rows: usize,
-    cols: usize,
+    map: Grid<Height>,
 }
 
 impl Heightmap {
-    pub fn new(heights: &Vec<Vec<Height>>) -> Heightmap {
-        let map = heights.clone();
-        let rows = heights.len();
-        let cols = heights[0].len();
-        Heightmap { map, rows, cols }
+    pub fn new(heights: &Grid<Height>) -> Heightmap {
+        Heightmap { map: heights.clone() }
     }
 
     fn low_points(&self) -> Vec<(Coord, Height)> {
-        let mut low_points = Vec::new();
-
-        for (r, row) in self.map.iter().enumerate() {
-            for (c, _) in row.iter().enumerate() {
-                if let Some(height) = self.test_low_point(r, c) {
-                    low_points.push(((r, c), height));
-                }
-            }
-        }
-
-        low_points
+        self.map.iter()
+            .filter(|&(coord, &height)| self.map.neighbors4(coord).all(|n| self.map[n] > height))
+            .map(|(coord, &height)| (coord, height))
+            .collect()
     }
 
     fn low_coords(&self) -> Vec<Coord>{
@@ -45,78 +66,34 @@ impl Heightmap {
         self.low_heights().iter().map(|v| v + 1).sum()
     }
 
-    fn test_low_point(&self, row: usize, col: usize) -> Option<Height> {
-        let height = self.map[row][col];
-        for r in Self::neighbors(row, self.rows - 1).iter().copied() {
-            if r == row { continue; }
-            if self.map[r][col] <= height {
-                return None
-            }
-        }
-
-        for c in Self::neighbors(col, self.cols - 1).iter().copied() {
-            if c == col { continue; }
-            if self.map[row][c] <= height {
-                return None;
-            }
-        }
-
-        return Some(height);
-    }
-
-    fn neighbors(num: usize, max: usize) -> Box<[usize]> {
-        match num {
-            0 => Box::new([0, 1]),
-            v if v == max => Box::new([max - 1, max]),
-            v if v < max => Box::new([num-1, num, num + 1]),
-            _ => Box::new([]),
-        }
-    }
-
     pub fn basins(&self) -> Vec<Basin> {
-        self.low_coords().iter().map(|p| self.basin(p.0, p.1)).collect()
+        self.low_coords().iter().map(|&p| self.basin(p)).collect()
     }
 
-    fn basin(&self, row: usize, col: usize) -> Basin {
+    fn basin(&self, low_point: Coord) -> Basin {
         let mut basin = Basin::new();
         let mut next = Vec::new();
-        next.push((row, col));
+        next.push(low_point);
 
-        'construct: while next.len() > 0 {
-            let (row, col) = next.pop().unwrap();
-            let height = self.map[row][col];
+        'construct: while let Some(coord) = next.pop() {
+            let height = self.map[coord];
 
             let mut neighbors = Vec::new();
 
-            for r in Self::neighbors(row, self.rows - 1).iter().copied() {
-                if r == row { continue; }
-                let rheight = self.map[r][col];
-                if basin.contains(&(r, col)) {
-                    // no action
-                } else if rheight >= height {
-                    if rheight < 9 {
-                        neighbors.push((r, col));
-                    }
-                } else {
-                    continue 'construct;
-                }
-            }
-    
-            for c in Self::neighbors(col, self.cols - 1).iter().copied() {
-                if c == col { continue; }
-                let cheight = self.map[row][c];
-                if basin.contains(&(row, c)) {
+            for neighbor in self.map.neighbors4(coord) {
+                let nheight = self.map[neighbor];
+                if basin.contains(&neighbor) {
                     // no action
-                } else if cheight >= height {
-                    if cheight < 9 {
-                        neighbors.push((row, c));
+                } else if nheight >= height {
+                    if nheight < 9 {
+                        neighbors.push(neighbor);
                     }
                 } else {
                     continue 'construct;
                 }
             }
 
-            basin.insert((row, col));
+            basin.insert(coord);
             next.append(&mut neighbors);
         }
 
@@ -140,7 +117,7 @@ mod tests {
 
         let basin_size = heightmap.iter().flatten().filter(|v| **v == 0 || **v == 1).count();
 
-        let heightmap = Heightmap::new(&heightmap);
+        let heightmap = Heightmap::new(&Grid::from_rows(heightmap));
         let basins = heightmap.basins();
 
         assert_eq!(1, basins.len());
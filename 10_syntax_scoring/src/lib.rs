@@ -1,3 +1,57 @@
+use common::{Answer, Counter, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(10, "Syntax Scoring", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let chunks: Vec<String> = input.lines().map(|line| {
+            match line.find(|c| !"()[]{}<>".contains(c)) {
+                Some(i) => Err(ParseError::at(input, &line[i..], "chunks may only contain brackets")),
+                None => Ok(String::from(line)),
+            }
+        }).collect::<Result<_, _>>()?;
+        if chunks.iter().all(String::is_empty) {
+            return Err(ParseError::new(1, 1, "there are no lines of chunks"));
+        }
+        Ok(chunks)
+    }
+
+    fn part_one(chunks: &Self::Input) -> Answer {
+        let mut counter = Counter::new();
+
+        chunks.iter()
+            .filter_map(|chunk| {
+                if let ParseResult::Corrupted { actual, .. } = parse_chunk(chunk) {
+                    Some(actual)
+                } else { None }
+            })
+            .for_each(|char| counter.add(char));
+
+        let result: u32 = counter.with_count_ge(1).iter()
+            .map(|c| counter.count(c) * score_corrupted(**c))
+            .sum();
+
+        result.into()
+    }
+
+    fn part_two(chunks: &Self::Input) -> Answer {
+        let mut completions: Vec<u128> = chunks.iter()
+            .filter_map(|chunk| {
+                if let ParseResult::Incomplete(completion) = parse_chunk(chunk) {
+                    Some(score_incomplete(&completion))
+                } else { None }
+            }).collect();
+
+        completions.sort();
+
+        completions[completions.len() / 2].into()
+    }
+}
+
 pub fn parse_chunk(chunks: &str) -> ParseResult {
     let mut closers = Vec::new();
 
@@ -19,10 +73,10 @@ pub fn parse_chunk(chunks: &str) -> ParseResult {
         }
     }
 
-    if closers.len() > 0 {
-        return ParseResult::Incomplete(closers.iter().rev().collect());
+    if !closers.is_empty() {
+        ParseResult::Incomplete(closers.iter().rev().collect())
     } else {
-        return ParseResult::Ok;
+        ParseResult::Ok
     }
 }
 
@@ -96,4 +150,11 @@ mod tests {
         let expected = 1_480_781;
         assert_eq!(expected, score_incomplete(completion));
     }
+
+    #[test]
+    fn parse_no_lines() {
+        let error = Solver::parse("\n\n").err().unwrap();
+
+        assert_eq!("line 1, column 1: there are no lines of chunks", error.to_string());
+    }
 }
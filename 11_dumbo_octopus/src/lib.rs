@@ -1,54 +1,73 @@
+use common::{Answer, Coord, Day, Grid, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(11, "Dumbo Octopus", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Grid<Octopus>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_octopi(input)
+    }
+
+    fn part_one(octopi: &Self::Input) -> Answer {
+        let mut octopi = OctopusGrid::new(octopi);
+
+        (0..100).for_each(|_| { octopi.step(); });
+
+        octopi.flashes().into()
+    }
+
+    fn part_two(octopi: &Self::Input) -> Answer {
+        let mut octopi = OctopusGrid::new(octopi);
+
+        let mut step: u32 = 1;
+        while !octopi.step() {
+            step += 1;
+        }
+
+        step.into()
+    }
+}
+
+fn parse_octopi(input: &str) -> Result<Grid<Octopus>, ParseError> {
+    Grid::parse(input, |c| c.to_digit(10).ok_or_else(|| format!("`{}` is not a energy level", c)))
+}
+
 pub type Octopus = u32;
-type Coord = (usize, usize);
 
 pub struct OctopusGrid {
-    grid: Vec<Vec<Octopus>>,
-    rows: usize,
-    cols: usize,
+    grid: Grid<Octopus>,
     flashes: u32,
 }
 
 impl OctopusGrid {
-    pub fn new(octopi: &Vec<Vec<Octopus>>) -> OctopusGrid {
-        let grid = octopi.clone();
-        let rows = octopi.len();
-        let cols = octopi[0].len();
-        OctopusGrid { grid, rows, cols, flashes: 0 }
+    pub fn new(octopi: &Grid<Octopus>) -> OctopusGrid {
+        OctopusGrid { grid: octopi.clone(), flashes: 0 }
     }
 
     // returns whether octopi are synchronized
     pub fn step(&mut self) -> bool {
-        let mut flashes: Vec<Vec<bool>> = self.grid.iter().map(|row| row.iter().map(|_| false).collect()).collect();
+        let mut flashes = self.grid.map(|_| false);
 
-        for r in 0..self.grid.len() {
-            for c in 0..self.grid[r].len() {
-                self.energize((r, c), &mut flashes);
-            }
+        for coord in self.grid.coords() {
+            self.energize(coord, &mut flashes);
         }
 
-        flashes.iter().map(|row| row.iter().copied().reduce(|a, b| a && b).unwrap()).reduce(|a, b| a && b).unwrap()
-    }
-    
-    fn neighbors(num: usize, max: usize) -> Box<[usize]> {
-        match num {
-            0 => Box::new([0, 1]),
-            v if v == max => Box::new([max - 1, max]),
-            v if v < max => Box::new([num-1, num, num + 1]),
-            _ => Box::new([]),
-        }
+        flashes.values().all(|&flashed| flashed)
     }
 
-    fn energize(&mut self, cd: Coord, flashes: &mut Vec<Vec<bool>>) {
-        if !flashes[cd.0][cd.1] {
-            self.grid[cd.0][cd.1] += 1;
-            if self.grid[cd.0][cd.1] > 9 {
-                self.grid[cd.0][cd.1] = 0;
-                flashes[cd.0][cd.1] = true;
+    fn energize(&mut self, cd: Coord, flashes: &mut Grid<bool>) {
+        if !flashes[cd] {
+            self.grid[cd] += 1;
+            if self.grid[cd] > 9 {
+                self.grid[cd] = 0;
+                flashes[cd] = true;
                 self.flashes += 1;
-                for r in Self::neighbors(cd.0, self.rows - 1).iter().copied() {
-                    for c in Self::neighbors(cd.1, self.cols -1).iter().copied() {
-                        if (r, c) != cd { self.energize((r, c), flashes) }
-                    }
+                let neighbors: Vec<_> = self.grid.neighbors8(cd).collect();
+                for neighbor in neighbors {
+                    self.energize(neighbor, flashes);
                 }
             }
         }
@@ -64,8 +83,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let octopi = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(1656), Solver::part_one(&octopi));
+        assert_eq!(Answer::Number(195), Solver::part_two(&octopi));
     }
 }
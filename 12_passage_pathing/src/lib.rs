@@ -1,39 +1,83 @@
 use std::collections::{HashMap, HashSet};
+use common::{Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(12, "Passage Pathing", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = CaveSystem;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(CaveSystem::new(parse_caves(input)?))
+    }
+
+    fn part_one(cave_system: &Self::Input) -> Answer {
+        let paths = cave_system.unique_paths("start", "end");
+
+        paths.len().into()
+    }
+
+    fn part_two(cave_system: &Self::Input) -> Answer {
+        let paths = cave_system.unique_paths_twice("start", "end");
+
+        paths.len().into()
+    }
+}
+
+fn parse_caves(input: &str) -> Result<Vec<Cave>, ParseError> {
+    let caves: Vec<Cave> = input.lines()
+        .map(|line| {
+            let (a, b) = line.split_once('-')
+                .ok_or_else(|| ParseError::at(input, line, "expected two caves separated by `-`"))?;
+            for name in [a, b] {
+                let is_small = name.chars().all(|c| c.is_ascii_lowercase());
+                let is_big = name.chars().all(|c| c.is_ascii_uppercase());
+                if name.is_empty() || !(is_small || is_big) {
+                    return Err(ParseError::at(input, name, format!("invalid cave `{}`", name)));
+                }
+            }
+            Ok(Cave(String::from(a), String::from(b)))
+        }).collect::<Result<_, _>>()?;
+
+    for name in ["start", "end"] {
+        if !caves.iter().any(|cave| cave.0 == name || cave.1 == name) {
+            return Err(ParseError::at(input, &input[input.len()..], format!("there is no `{}` cave", name)));
+        }
+    }
+    Ok(caves)
+}
 
 pub struct Cave(pub String, pub String);
 
 pub struct CaveSystem {
     adjacency: HashMap<String, Vec<String>>,
-    big: HashSet<String>,
     small: HashSet<String>,
 }
 
 impl CaveSystem {
     pub fn new(caves: Vec<Cave>) -> CaveSystem {
-        let mut adjacency = HashMap::new();
-        let mut big = HashSet::new();
+        let mut adjacency: HashMap<String, Vec<String>> = HashMap::new();
         let mut small = HashSet::new();
 
         for cave in caves.iter() {
             for c in [&cave.0, &cave.1] {
                 if c.to_lowercase() == *c {
                     small.insert(c.clone());
-                } else if c.to_uppercase() == *c {
-                    big.insert(c.clone());
-                } else {
+                } else if c.to_uppercase() != *c {
                     panic!("invalid cave {}", c);
                 }
             }
 
             for adj in [(&cave.0, &cave.1), (&cave.1, &cave.0)] {
-                let entry = adjacency.entry(adj.0.clone()).or_insert(Vec::new());
+                let entry = adjacency.entry(adj.0.clone()).or_default();
                 if !entry.contains(adj.1) {
                     entry.push(adj.1.clone());
                 }
             }
         }
 
-        CaveSystem { adjacency, big, small }
+        CaveSystem { adjacency, small }
     }
 
     pub fn unique_paths(&self, start: &str, end: &str) -> Vec<Vec<String>> {
@@ -108,8 +152,17 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let cave_system = Solver::parse(include_str!("../input/example1.txt")).unwrap();
+        assert_eq!(Answer::Number(10), Solver::part_one(&cave_system));
+        assert_eq!(Answer::Number(36), Solver::part_two(&cave_system));
+    }
+
+    #[test]
+    fn missing_start() {
+        let error = Solver::parse("A-b\nb-end\n").err().unwrap();
+
+        assert_eq!("line 3, column 1: there is no `start` cave", error.to_string());
+        assert!(Solver::parse("").is_err());
     }
 }
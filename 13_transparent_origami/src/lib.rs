@@ -1,5 +1,64 @@
-use std::collections::{HashSet};
 use std::fmt;
+use common::{parse_at, Answer, Day, ParseError, Point, SparseGrid, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(13, "Transparent Origami", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = (Vec<Dot>, Vec<Fold>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_instructions(input)
+    }
+
+    fn part_one((dots, folds): &Self::Input) -> Answer {
+        let mut paper = DottedPaper::new(dots);
+        paper.fold(&folds[0]);
+
+        paper.dot_count().into()
+    }
+
+    fn part_two((dots, folds): &Self::Input) -> Answer {
+        let mut paper = DottedPaper::new(dots);
+        for fold in folds {
+            paper.fold(fold);
+        }
+
+        paper.to_string().into()
+    }
+}
+
+fn parse_instructions(input: &str) -> Result<(Vec<Dot>, Vec<Fold>), ParseError> {
+    let mut lines = input.lines();
+
+    // dots come first, up to a blank line
+    let mut dots = Vec::new();
+    for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+        let (x, y) = line.split_once(',')
+            .ok_or_else(|| ParseError::at(input, line, "expected a dot like `6,10`"))?;
+        dots.push(Dot { x: parse_at(input, x)?, y: parse_at(input, y)? });
+    }
+
+    let mut folds = Vec::new();
+    for line in lines.filter(|line| !line.is_empty()) {
+        let (axis, at) = line.strip_prefix("fold along ")
+            .and_then(|fold| fold.split_once('='))
+            .ok_or_else(|| ParseError::at(input, line, "expected a fold like `fold along y=7`"))?;
+        let axis = match axis {
+            "x" => Axis::X,
+            "y" => Axis::Y,
+            _ => return Err(ParseError::at(input, axis, format!("invalid axis `{}`", axis))),
+        };
+        folds.push(Fold { axis, line: parse_at(input, at)? });
+    }
+
+    if folds.is_empty() {
+        return Err(ParseError::at(input, &input[input.len()..], "expected at least one fold"));
+    }
+
+    Ok((dots, folds))
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Dot {
@@ -19,67 +78,49 @@ pub struct Fold {
 }
 
 pub struct DottedPaper {
-    dots: HashSet<Dot>,
+    dots: SparseGrid<bool>,
 }
 
 impl DottedPaper {
-    pub fn new(dots: &Vec<Dot>) -> DottedPaper {
-        let dots = dots.iter().copied().collect();
+    pub fn new(dots: &[Dot]) -> DottedPaper {
+        let dots = dots.iter().map(|d| (Self::point(d), true)).collect();
         DottedPaper { dots }
     }
 
     pub fn fold(&mut self, fold: &Fold) {
-        self.dots = self.dots.iter().map(|d| Self::fold_dot(d, fold)).collect();
+        self.dots = self.dots.iter().map(|(p, _)| (Self::fold_point(p, fold), true)).collect();
     }
 
     pub fn dot_count(&self) -> usize {
         self.dots.len()
     }
 
-    fn fold_dot(dot: &Dot, fold: &Fold) -> Dot {
-        let x = if fold.axis == Axis::X && dot.x > fold.line {
-            (2 * fold.line) - dot.x
-        } else { 
-            dot.x 
-        };
+    fn point(dot: &Dot) -> Point {
+        Point::new(dot.x.into(), dot.y.into())
+    }
+
+    fn fold_point(point: Point, fold: &Fold) -> Point {
+        let line = fold.line.into();
 
-        let y = if fold.axis == Axis::Y && dot.y > fold.line {
-            (2 * fold.line) - dot.y
-        } else { 
-            dot.y 
+        let x = if fold.axis == Axis::X && point.x > line {
+            (2 * line) - point.x
+        } else {
+            point.x
         };
 
-        Dot { x, y }
-    }
+        let y = if fold.axis == Axis::Y && point.y > line {
+            (2 * line) - point.y
+        } else {
+            point.y
+        };
 
-    fn max(&self) -> Dot {
-        let mut dot_iter = self.dots.iter();
-        let mut max = *dot_iter.next().unwrap();
-        for dot in dot_iter {
-            max.x = std::cmp::max(dot.x, max.x);
-            max.y = std::cmp::max(dot.y, max.y);
-        }
-        max
+        Point::new(x, y)
     }
 }
 
 impl fmt::Display for DottedPaper {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut result = String::new();
-        let dimensions = self.max();
-        for r in 0..=dimensions.y {
-            for c in 0..=dimensions.x {
-                if self.dots.contains(&Dot{ x: c, y: r }) {
-                    result.push('█');
-                } else {
-                    result.push(' ');
-                }
-            }
-            result.push('\n');
-        }
-
-
-        write!(f, "{}", result.trim())
+        write!(f, "{}", self.dots.render(|&dot| if dot { '█' } else { ' ' }))
     }
 }
 
@@ -90,10 +131,8 @@ mod tests {
     #[test]
     fn x_fold() {
         let mut paper = DottedPaper::new(
-            &vec![
-                Dot { x: 1, y: 0 },
-                Dot { x: 9, y: 0 },
-            ]
+            &[Dot { x: 1, y: 0 },
+                Dot { x: 9, y: 0 }]
         );
 
         let fold = Fold { axis: Axis::X, line: 5 };
@@ -105,12 +144,10 @@ mod tests {
     #[test]
     fn y_fold() {
         let mut paper = DottedPaper::new(
-            &vec![
-                Dot { x: 4, y: 5 },
+            &[Dot { x: 4, y: 5 },
                 Dot { x: 4, y: 3 },
                 Dot { x: 7, y: 0 },
-                Dot { x: 7, y: 8 },
-            ]
+                Dot { x: 7, y: 8 }]
         );
 
         let fold = Fold { axis: Axis::Y, line: 4 };
@@ -122,10 +159,8 @@ mod tests {
     #[test]
     fn two_folds() {
         let mut paper = DottedPaper::new(
-            &vec![
-                Dot { x: 102, y: 1 },
-                Dot { x: 54, y: 5 }
-            ]
+            &[Dot { x: 102, y: 1 },
+                Dot { x: 54, y: 5 }]
         );
 
         let folds = vec! [
@@ -1,5 +1,77 @@
 use std::collections::HashMap;
-use common::{char_windows};
+use common::{char_windows, Answer, Counter, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(14, "Extended Polymerization", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    // the polymer template, and the rules to grow it by
+    type Input = (String, PolyRuleSet);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_polymerizer(input)
+    }
+
+    fn part_one((template, polymerizer): &Self::Input) -> Answer {
+        let mut polymer = template.clone();
+        for _ in 0..10 {
+            polymer = polymerizer.polymerize(&polymer);
+        }
+
+        let counter: Counter<char> = polymer.chars().collect();
+
+        let most_frequent = counter.most_frequent().unwrap();
+        let least_frequent = counter.least_frequent().unwrap();
+
+        (counter.count(most_frequent) - counter.count(least_frequent)).into()
+    }
+
+    fn part_two((template, polymerizer): &Self::Input) -> Answer {
+        let mut element_counter: Counter<char, u128> = template.chars().collect();
+        let mut pair_counter: Counter<&str, u128> = char_windows(template, 2).collect();
+
+        for _i in 0..40 {
+            let mut new_counter = Counter::new();
+            for (key, count) in pair_counter.iter() {
+                polymerizer.polymer_pairs(key).unwrap().iter().for_each(|w| new_counter.add_count(*w, *count));
+                element_counter.add_count(polymerizer.polymer(key).unwrap().chars().nth(1).unwrap(), *count);
+            }
+            pair_counter = new_counter;
+        }
+
+        let most_frequent = element_counter.most_frequent().unwrap();
+        let least_frequent = element_counter.least_frequent().unwrap();
+
+        (element_counter.count(most_frequent) - element_counter.count(least_frequent)).into()
+    }
+}
+
+fn parse_polymerizer(input: &str) -> Result<(String, PolyRuleSet), ParseError> {
+    let mut lines = input.lines();
+
+    let template = lines.next()
+        .filter(|template| !template.is_empty())
+        .ok_or_else(|| ParseError::new(1, 1, "expected a polymer template"))?;
+    if let Some(i) = template.find(|c: char| !c.is_ascii_uppercase()) {
+        return Err(ParseError::at(input, &template[i..], "elements must be uppercase letters"));
+    }
+
+    let rules = lines.filter(|line| !line.is_empty()).map(|line| {
+        let (pair, out) = line.split_once("->")
+            .ok_or_else(|| ParseError::at(input, line, "expected a rule like `CH -> B`"))?;
+        let (pair, out) = (pair.trim(), out.trim());
+        if pair.len() != 2 || !pair.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(ParseError::at(input, pair, "a rule must match a pair of elements"));
+        }
+        match out.chars().collect::<Vec<_>>()[..] {
+            [out] if out.is_ascii_uppercase() => Ok(PolyRule::new(pair, out)),
+            _ => Err(ParseError::at(input, out, "a rule must insert a single element")),
+        }
+    }).collect::<Result<_, _>>()?;
+
+    Ok((String::from(template), PolyRuleSet::from(rules)))
+}
 
 pub struct PolyRule {
     pair: String,
@@ -16,7 +88,7 @@ impl PolyRule {
         PolyRule {
             pair: String::from(pair),
             out,
-            polymer: polymer,
+            polymer,
         }
     }
 
@@ -46,11 +118,7 @@ impl PolyRuleSet {
     }
 
     pub fn polymer(&self, pair: &str) -> Option<&str> {
-        if let Some(out) = self.rules.get(pair) {
-            Some(&out)
-        } else {
-            None
-        }
+        self.rules.get(pair).map(|out| &out[..])
     }
 
     pub fn polymer_pairs(&self, pair: &str) -> Option<[&str; 2]> {
@@ -72,76 +140,6 @@ impl PolyRuleSet {
     }
 }
 
-use std::hash::Hash;
-
-pub struct Counter<T> 
-    where T: Eq + Hash
-{
-    counts: HashMap<T, u128>,
-}
-
-impl<T> Counter<T> 
-    where T: Eq + Hash
-{
-    pub fn new() -> Counter<T> {
-        Counter {
-            counts: HashMap::new(),
-        }
-    }
-
-    pub fn add(&mut self, item: T) {
-        let entry = self.counts.entry(item).or_insert(0);
-        *entry += 1;
-    }
-
-    pub fn add_count(&mut self, item: T, count: u128) {
-        let entry = self.counts.entry(item).or_insert(0);
-        *entry += count;
-    }
-
-    pub fn count(&self, item: T) -> Option<u128> {
-        self.counts.get(&item).copied()
-    }
-
-    pub fn keys(&self) -> impl Iterator<Item = &T> {
-        self.counts.keys()
-    }
-
-    pub fn iter(&self) -> impl Iterator<Item = (&T, &u128)> {
-        self.counts.iter()
-    }
-
-    pub fn with_count_ge(&self, min: u128) -> Vec<&T> {
-        self.counts.iter().filter_map(|(key, value)| {
-            if *value >= min {
-                Some(key)
-            } else {
-                None
-            }
-        }).collect()
-    }
-
-    pub fn most_frequent(&self) -> Option<&T> {
-        self.counts.iter().fold((None, 0), |(max_key, max_count), (key, value)| {
-            if *value > max_count {
-                (Some(key), *value)
-            } else {
-                (max_key, max_count)
-            }
-        }).0
-    }
-
-    pub fn least_frequent(&self) -> Option<&T> {
-        self.counts.iter().fold((None, u128::MAX), |(min_key, min_count), (key, value)| {
-            if *value < min_count {
-                (Some(key), *value)
-            } else {
-                (min_key, min_count)
-            }
-        }).0
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,7 +167,7 @@ mod tests {
         let rules = make_ruleset();
 
         let input = "ABDE";
-        let actual = rules.polymerize(&input);
+        let actual = rules.polymerize(input);
         let expected = String::from("ACBJDFE");
         assert_eq!(expected, actual);
 
@@ -1,158 +1,79 @@
-use std::cmp::{Reverse};
-use priority_queue::PriorityQueue;
+use common::{Answer, Coord, Day, Grid, ParseError, Solution};
+use common::search::{self, Path, Successors};
 
+pub const DAY: Day = Day::new::<Solver>(15, "Chiton", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
 
-pub type Risk = u8;
+pub struct Solver;
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-pub struct Coord {
-    pub row: usize,
-    pub col: usize,
-}
+impl Solution for Solver {
+    type Input = Grid<Risk>;
 
-impl Coord {
-    pub fn new(row: usize, col: usize) -> Coord {
-        Coord{ row, col }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_risks(input)
     }
 
-    pub fn adj(&self) -> Box<[Coord]> {
-        let row_adj = Self::adj_axis(self.row);
-        let row_adj = row_adj.iter().map(|r| Coord::new(*r, self.col));
-        let col_adj = Self::adj_axis(self.col);
-        let col_adj = col_adj.iter().map(|c| Coord::new(self.row, *c));
-        let adj = row_adj.chain(col_adj).collect();
-        adj
+    fn part_one(risks: &Self::Input) -> Answer {
+        safest_path(risks).cost.into()
     }
 
-    pub fn adj_bounded(&self, max_row: usize, max_col: usize) -> Box<[Coord]> {
-        let row_adj = Self::adj_axis_bounded(self.row, max_row);
-        let row_adj = row_adj.iter().map(|r| Coord::new(*r, self.col));
-        let col_adj = Self::adj_axis_bounded(self.col, max_col);
-        let col_adj = col_adj.iter().map(|c| Coord::new(self.row, *c));
-        let adj = row_adj.chain(col_adj).collect();
-        adj
-    }
+    fn part_two(starting_grid: &Self::Input) -> Answer {
+        let factor = 5;
+        let full_grid = multiply_grid(starting_grid, factor);
 
-    fn adj_axis(coord: usize) -> Box<[usize]> {
-        match coord {
-            0 => Box::new([1]),
-            c => Box::new([c-1, c+1]),
-        }
-    }
-
-    fn adj_axis_bounded(coord: usize, bound: usize) -> Box<[usize]> {
-        let bound = bound - 1;
-        match coord {
-            0 => Box::new([1]),
-            c if c == bound => Box::new([c-1]),
-            c => Box::new([c-1, c+1]),
-        }
+        safest_path(&full_grid).cost.into()
     }
 }
 
-pub struct RiskGrid {
-    list: Vec<Risk>,
-    rows: usize,
-    cols: usize,
+fn parse_risks(input: &str) -> Result<Grid<Risk>, ParseError> {
+    // every step costs something, which the search relies on
+    Grid::parse(input, |c| match c.to_digit(10) {
+        Some(risk) if risk > 0 => Ok(risk as Risk),
+        _ => Err(format!("`{}` is not a risk level from 1 to 9", c)),
+    })
 }
 
-impl RiskGrid {
-    // pub fn from_grid(grid: &[&[Risk]]) -> RiskGrid {
-    //     assert!(grid.len() > 0, "grid is empty");
+// tiles the grid `factor` times in each direction, adding the tile's distance
+// from the top left to every risk and wrapping 9 around to 1
+fn multiply_grid(grid: &Grid<Risk>, factor: usize) -> Grid<Risk> {
+    let (rows, cols) = (grid.rows(), grid.cols());
 
-    //     let rows = grid.len();
-    //     let cols = grid[0].len();
-
-    //     let list = grid.iter().flat_map(|row| {
-    //         assert_eq!(cols, row.len(), "inconsistent row size");
-    //         row.iter().copied()
-    //     }).collect();
-
-    //     RiskGrid { rows, cols, list }
-    // }
-
-    pub fn from_list(list: &[Risk], row_size: usize) -> RiskGrid {
-        assert!(list.len() % row_size == 0, "cannot fill all rows");
-        assert!(list.len() > 0, "list is empty");
+    Grid::from_fn(rows * factor, cols * factor, |c| {
+        let risk = grid[Coord::new(c.row % rows, c.col % cols)];
+        let add = c.row / rows + c.col / cols;
+        add_clamp(risk as usize, add, 1, 9) as Risk
+    })
+}
 
-        let rows = list.len() / row_size;
-        let cols = row_size;
-        let list = list.iter().copied().collect();
+fn add_clamp(start: usize, add: usize, min: usize, max: usize) -> usize {
+    let modulus = max - min + 1;
+    (((start - min) + add) % modulus) + min
+}
 
-        RiskGrid{ rows, cols, list }
-    }
 
-    pub fn get(&self, row: usize, col: usize) -> Option<Risk> {
-        self.get_coord(Coord::new(row, col))
-    }
+pub type Risk = u8;
 
-    fn get_coord(&self, c: Coord) -> Option<Risk> {
-        if let Some(i) = self.ci(c) {
-            Some(self.list[i])
-        } else {
-            None
-        }
-    }
+/// The safest path from the top left to the bottom right, including both, and
+/// its total risk, not counting the starting position.
+pub fn safest_path(risks: &Grid<Risk>) -> Path<Coord, u32> {
+    let start = Coord::new(0, 0);
+    let end = Coord::new(risks.rows() - 1, risks.cols() - 1);
 
-    fn ci(&self, c: Coord) -> Option<usize> {
-        let i = (self.rows * c.row) + c.col;
-        if i < self.list.len() {
-            Some(i)
-        } else {
-            None
-        }
-    }
+    // every step risks at least 1, so the distance left never overestimates
+    let distance = |c: &Coord| ((end.row - c.row) + (end.col - c.col)) as u32;
 
-    fn ic(&self, index: usize) -> Option<Coord> {
-        if index < self.list.len() {
-            Some(Coord::new(index / self.rows, index % self.rows))
-        } else {
-            None
-        }
-    }
+    search::astar(&Cavern(risks), start, |&c| c == end, distance)
+        .expect("the bottom right is always reachable")
+}
 
-    pub fn safest_path(&self) -> Vec<Coord> {
-        let start = self.ci(Coord::new(0, 0)).unwrap();
-        let end = self.ci(Coord::new(self.rows - 1, self.cols - 1)).unwrap();
-
-        let mut distances: Vec<Option<u32>> = (0..self.list.len()).map(|_| None).collect();
-        let mut previous: Vec<Option<usize>> = (0..self.list.len()).map(|_| None).collect();
-        let mut priority = PriorityQueue::new();
-
-        distances[start] = Some(0);
-        priority.push(start, Reverse(0));
-
-        'dijkstra: while let Some((current, Reverse(risk))) = priority.pop() {
-            if current == end { break 'dijkstra; }
-
-            for neighbor in self.ic(current).unwrap().adj_bounded(self.rows, self.cols).iter().map(|c| self.ci(*c).unwrap()) {
-                let new: u32 = risk as u32 + self.list[neighbor] as u32;
-                if let Some(old) = distances[neighbor] {
-                    if new < old {
-                        distances[neighbor] = Some(new);
-                        previous[neighbor] = Some(current);
-                        if let None = priority.change_priority(&neighbor, Reverse(new)) {
-                            priority.push(neighbor, Reverse(new));
-                        }
-                    }
-                } else {
-                    distances[neighbor] = Some(new);
-                    previous[neighbor] = Some(current);
-                    priority.push(neighbor, Reverse(new));
-                }
-            }
-        }
+// the cavern as a graph of positions, where entering one costs its risk
+struct Cavern<'a>(&'a Grid<Risk>);
 
-        let mut path = Vec::new();
-        let mut trace = Some(end);
-        
-        while let Some(current) = trace {
-            path.push(self.ic(current).unwrap());
-            trace = previous[current];
-        } 
+impl Successors for Cavern<'_> {
+    type State = Coord;
+    type Cost = u32;
 
-        path.reverse();
-        path
+    fn successors(&self, &c: &Coord) -> impl IntoIterator<Item = (Coord, u32)> {
+        self.0.neighbors4(c).map(|neighbor| (neighbor, self.0[neighbor] as u32))
     }
 }
 
@@ -161,19 +82,42 @@ mod tests {
     use super::*;
 
     #[test]
+    fn safest_path_example() {
+        let risks = parse_risks("116\n138\n213").unwrap();
+        let path = safest_path(&risks);
+
+        assert_eq!(7, path.cost);
+        assert_eq!(Coord::new(0, 0), path.states[0]);
+        assert_eq!(Some(&Coord::new(2, 2)), path.states.last());
+        assert_eq!(5, path.states.len());
+    }
 
-    fn from_list() {
-        let list = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+    #[test]
+    fn parse_zero_risk() {
+        let error = parse_risks("116\n108\n213").unwrap_err();
 
-        let grid = RiskGrid::from_list(&list[..], 3);
+        assert_eq!((2, 2), (error.line(), error.column()));
+        assert_eq!("`0` is not a risk level from 1 to 9", error.explanation());
+    }
 
-        assert_eq!(5, grid.get(1, 1).unwrap());
+    #[test]
+    fn multiply() {
+        let grid = Grid::from_rows(vec![vec![8]]);
+        let expected = Grid::from_rows(vec![
+            vec![8, 9, 1, 2, 3],
+            vec![9, 1, 2, 3, 4],
+            vec![1, 2, 3, 4, 5],
+            vec![2, 3, 4, 5, 6],
+            vec![3, 4, 5, 6, 7],
+        ]);
+
+        assert_eq!(expected, multiply_grid(&grid, 5));
     }
 
     #[test]
     fn coord_neighbors_bounded() {
         let c = Coord::new(7, 5);
-        let neighbors = c.adj_bounded(100, 100);
+        let neighbors: Vec<_> = Grid::new(100, 100, 0).neighbors4(c).collect();
         let expected = vec![
             Coord::new(7, 4),
             Coord::new(7, 6),
@@ -191,7 +135,7 @@ mod tests {
     #[test]
     fn coord_neighbors_bounded_max_row() {
         let c = Coord::new(7, 5);
-        let neighbors = c.adj_bounded(8, 100);
+        let neighbors: Vec<_> = Grid::new(8, 100, 0).neighbors4(c).collect();
         let expected = vec![
             Coord::new(7, 4),
             Coord::new(7, 6),
@@ -208,7 +152,7 @@ mod tests {
     #[test]
     fn coord_neighbors_bounded_max_col() {
         let c = Coord::new(4, 6);
-        let neighbors = c.adj_bounded(17, 7);
+        let neighbors: Vec<_> = Grid::new(17, 7, 0).neighbors4(c).collect();
         let expected = vec![
             Coord::new(3, 6),
             Coord::new(5, 6),
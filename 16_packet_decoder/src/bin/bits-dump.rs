@@ -0,0 +1,69 @@
+//! Print each packet of BITS transmissions as an indented tree, along with
+//! the bits it occupies.
+//!
+//! Usage: `bits-dump [--simplify] [HEX]...`, reading one transmission per line
+//! from stdin when none are given. With `--simplify`, print each expression
+//! and its simplified form instead, re-encoded as a transmission.
+
+use std::env;
+use std::io::{self, BufRead};
+use std::process;
+
+use packet_decoder::packet::{dump_hex, encode_hex, parse_hex, simplify, Packet};
+
+fn main() {
+    let (flags, mut transmissions): (Vec<String>, Vec<String>) = env::args().skip(1)
+        .partition(|arg| arg.starts_with('-'));
+    let simplified = match flags.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => false,
+        ["-s"] | ["--simplify"] => true,
+        _ => {
+            eprintln!("usage: bits-dump [--simplify] [HEX]...");
+            process::exit(2);
+        },
+    };
+    if transmissions.is_empty() {
+        transmissions = io::stdin().lock().lines()
+            .map(|line| line.unwrap_or_else(|e| panic!("could not read stdin: {}", e)))
+            .collect();
+    }
+
+    let mut success = true;
+    for (i, hex) in transmissions.iter().map(|hex| hex.trim()).filter(|hex| !hex.is_empty()).enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let lines = if simplified {
+            simplify_hex(hex)
+        } else {
+            dump_hex(hex).map(|entries| entries.iter().map(ToString::to_string).collect()).map_err(|e| e.to_string())
+        };
+        match lines {
+            Ok(lines) => {
+                for line in lines {
+                    println!("{}", line);
+                }
+            },
+            Err(e) => {
+                eprintln!("transmission {}: {}", i + 1, e);
+                success = false;
+            },
+        }
+    }
+
+    if !success {
+        process::exit(1);
+    }
+}
+
+fn simplify_hex(hex: &str) -> Result<Vec<String>, String> {
+    let packet = parse_hex(hex).map_err(|e| e.to_string())?;
+    let simple: Box<dyn Packet> = simplify(&packet.to_node()).into();
+    let simple_hex = encode_hex(&*simple).map_err(|e| e.to_string())?;
+
+    Ok(vec![
+        format!("expression: {}", packet.eval_string()),
+        format!("simplified: {}", simple.eval_string()),
+        format!("encoded:    {} ({} hex digits)", simple_hex, simple_hex.len()),
+    ])
+}
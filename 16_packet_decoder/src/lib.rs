@@ -1,46 +1,135 @@
+mod packet_dump;
+mod packet_encode;
+mod packet_eval;
+mod packet_node;
 mod packet_parse;
+mod packet_simplify;
 mod packet_types;
 
+use common::{Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(16, "Packet Decoder", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Box<dyn packet::Packet>;
+
+    // a transmission that cannot be evaluated is rejected here, so that part
+    // two always has an answer
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let hex = input.lines().next()
+            .ok_or_else(|| ParseError::new(1, 1, "expected a line of hexadecimal"))?;
+        let packet = packet::parse_hex(hex).map_err(|e| e.within(input, hex))?;
+        value(&*packet).map_err(|e| ParseError::at(input, hex, format!("the transmission has no value: {}", e)))?;
+        Ok(packet)
+    }
+
+    fn part_one(packet: &Self::Input) -> Answer {
+        let result: u128 = packet.versions().iter().map(|v| *v as u128).sum();
+
+        result.into()
+    }
+
+    fn part_two(packet: &Self::Input) -> Answer {
+        value(&**packet).expect("`parse` only accepts transmissions with a value")
+    }
+}
+
+fn value(packet: &dyn packet::Packet) -> Result<Answer, packet::EvalError> {
+    match packet.evaluate() {
+        Ok(value) => Ok(value.into()),
+        #[cfg(feature = "bigint")]
+        Err(packet::EvalError::Overflow(_)) => packet.evaluate_big().map(|value| Answer::Text(value.to_string())),
+        Err(e) => Err(e),
+    }
+}
+
 pub mod packet {
-    use crate::packet_types::literal::Literal;
-    use crate::packet_types::operator::Operator;
-    use crate::packet_parse::{type_id};
+    use std::io::{Cursor, Read};
+    use common::ParseError;
+
+    use crate::packet_parse::{bin_to_bytes, decode_packet, hex_to_bytes};
+    pub use crate::packet_dump::{disassemble, DumpBody, DumpEntry};
+    pub use crate::packet_encode::{encode_hex, parse_expression, BitWriter, EncodeError};
+    pub use crate::packet_eval::{EvalError, Value};
+    pub use crate::packet_node::{PacketNode, Visitor};
+    pub use crate::packet_parse::{BitReader, DecodeError};
+    pub use crate::packet_simplify::simplify;
+    pub use crate::packet_types::{Header, OpKind};
+    pub use crate::packet_types::literal::Literal;
+    pub use crate::packet_types::operator::{LengthType, Operator};
+    #[cfg(feature = "bigint")]
+    pub use num_bigint::BigUint;
 
     pub trait Packet {
         fn versions(&self) -> Vec<u8>;
 
         fn values(&self) -> Vec<u128>;
 
-        fn evaluate(&self) -> u128;
+        /// The value of the expression, or why it has none. Values that do not
+        /// fit in a `u128` are an `EvalError::Overflow`.
+        fn evaluate(&self) -> Result<u128, EvalError>;
+
+        /// The value of the expression, however large.
+        #[cfg(feature = "bigint")]
+        fn evaluate_big(&self) -> Result<BigUint, EvalError>;
 
         fn eval_string(&self) -> String;
+
+        fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError>;
+
+        /// A copy of the packet tree that can be inspected.
+        fn to_node(&self) -> PacketNode;
     }
 
-    pub fn parse_hex(hex: &str) -> Box<dyn Packet> {
-        let binary = hex_to_bin(hex);
-        parse_bin(&binary)
+    /// A literal packet. Panics if `version` does not fit in 3 bits.
+    pub fn literal(version: u8, value: u128) -> Box<dyn Packet> {
+        assert!(version < 8, "versions are 3 bits");
+        Box::new(Literal::new(version, value))
     }
-    
-    fn hex_to_bin(hex: &str) -> String {
-        hex.chars().map(|c| {
-            let num = c.to_digit(16).expect(&format!("char `{}` is not a hex digit", c));
-            format!("{:04b}", num)
-        }).collect()
+
+    /// An operator packet, which counts its subpackets unless there are too
+    /// many. Panics if `version` does not fit in 3 bits.
+    pub fn operator(version: u8, kind: OpKind, subpackets: Vec<Box<dyn Packet>>) -> Box<dyn Packet> {
+        assert!(version < 8, "versions are 3 bits");
+        Box::new(Operator::new(version, kind, subpackets))
     }
 
-    pub fn parse_bin(bin: &str) -> Box<dyn Packet> {
-        match type_id(bin) {
-            4 => literal(bin),
-            _id => operator(bin),
-        }
+    /// Decode the packet at the start of a transmission, ignoring anything
+    /// after it. Works on a `&[u8]` or any other `Read`.
+    pub fn decode(transmission: impl Read) -> Result<Box<dyn Packet>, DecodeError> {
+        decode_packet(&mut BitReader::new(transmission))
     }
 
-    pub fn literal(bin: &str) -> Box<dyn Packet> {
-        Box::new(Literal::new(bin))
+    pub fn parse_hex(hex: &str) -> Result<Box<dyn Packet>, ParseError> {
+        read_hex(hex, decode_packet)
     }
 
-    pub fn operator(bin: &str) -> Box<dyn Packet> {
-        Box::new(Operator::new(bin))
+    /// List every packet of a hex transmission along with the bits it was
+    /// decoded from.
+    pub fn dump_hex(hex: &str) -> Result<Vec<DumpEntry>, ParseError> {
+        read_hex(hex, disassemble)
+    }
+
+    // check that `hex` is all hex digits before reading it as bits
+    fn read_hex<T>(hex: &str, read: impl FnOnce(&mut BitReader<Cursor<Vec<u8>>>) -> Result<T, DecodeError>) -> Result<T, ParseError> {
+        if let Some((i, c)) = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+            return Err(ParseError::at(hex, &hex[i..], format!("`{}` is not a hex digit", &hex[i..i + c.len_utf8()])));
+        }
+
+        // each hex digit holds 4 bits, so an odd number of digits leaves half
+        // of the last byte unused
+        let mut bits = BitReader::with_limit(Cursor::new(hex_to_bytes(hex)), hex.len() * 4);
+        read(&mut bits).map_err(|e| ParseError::at(hex, &hex[e.bit() / 4..], e.to_string()))
+    }
+
+    /// Decode a packet written out as a string of '0's and '1's. Panics if it
+    /// is not a valid packet.
+    pub fn parse_bin(bin: &str) -> Box<dyn Packet> {
+        let bytes = bin_to_bytes(bin);
+        let mut bits = BitReader::with_limit(&bytes[..], bin.len());
+        decode_packet(&mut bits).unwrap_or_else(|e| panic!("invalid packet: {}", e))
     }
 }
 
@@ -78,7 +167,7 @@ mod tests {
     #[test]
     fn test_versions_example_0() {
         let hex = "D2FE28";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
         let versions = vec![6];
 
         assert_eq!(versions, packet.versions());
@@ -87,7 +176,7 @@ mod tests {
     #[test]
     fn test_versions_example_1() {
         let hex = "38006F45291200";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
         let versions = vec![1, 6, 2];
 
         assert_eq!(versions, packet.versions());
@@ -96,7 +185,7 @@ mod tests {
     #[test]
     fn test_versions_example_2() {
         let hex = "EE00D40C823060";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
         let versions = vec![7, 2, 4, 1];
 
         assert_eq!(versions, packet.versions());
@@ -109,7 +198,7 @@ mod tests {
     #[test]
     fn test_versions_example_3() {
         let hex = "8A004A801A8002F478";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
         let versions = vec![4, 1, 5, 6];
 
         assert_eq!(versions, packet.versions());
@@ -121,7 +210,7 @@ mod tests {
     #[test]
     fn test_versions_example_4() {
         let hex = "620080001611562C8802118E34";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
         assert_eq!(12_u128, packet.versions().iter().map(|&v| v as u128).sum());
     }
@@ -132,7 +221,7 @@ mod tests {
     #[test]
     fn test_versions_example_5() {
         let hex = "C0015000016115A2E0802F182340";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
         assert_eq!(23_u128, packet.versions().iter().map(|&v| v as u128).sum());
     }
@@ -144,7 +233,7 @@ mod tests {
     fn test_versions_example_6() {
         let hex = "A0016C880162017C3686B18A3D4780";
         
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
         assert_eq!(31_u128, packet.versions().iter().map(|&v| v as u128).sum());
     }
@@ -154,80 +243,158 @@ mod tests {
     #[test]
     fn test_eval_example_1() {
         let hex = "C200B40A82";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(3, packet.evaluate());
+        assert_eq!(3, packet.evaluate().unwrap());
     }
 
     // 04005AC33890 finds the product of 6 and 9, resulting in the value 54.
     #[test]
     fn test_eval_example_2() {
         let hex = "04005AC33890";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(54, packet.evaluate());
+        assert_eq!(54, packet.evaluate().unwrap());
     }
 
     // 880086C3E88112 finds the minimum of 7, 8, and 9, resulting in the value 7.
     #[test]
     fn test_eval_example_3() {
         let hex = "880086C3E88112";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(7, packet.evaluate());
+        assert_eq!(7, packet.evaluate().unwrap());
     }
 
     // CE00C43D881120 finds the maximum of 7, 8, and 9, resulting in the value 9.
     #[test]
     fn test_eval_example_4() {
         let hex = "CE00C43D881120";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(9, packet.evaluate());
+        assert_eq!(9, packet.evaluate().unwrap());
     }
 
     // D8005AC2A8F0 produces 1, because 5 is less than 15.
     #[test]
     fn test_eval_example_5() {
         let hex = "D8005AC2A8F0";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(1, packet.evaluate());
+        assert_eq!(1, packet.evaluate().unwrap());
     }
 
     // F600BC2D8F produces 0, because 5 is not greater than 15.
     #[test]
     fn test_eval_example_6() {
         let hex = "F600BC2D8F";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(0, packet.evaluate());
+        assert_eq!(0, packet.evaluate().unwrap());
     }
 
     // 9C005AC2F8F0 produces 0, because 5 is not equal to 15.
     #[test]
     fn test_eval_example_7() {
         let hex = "9C005AC2F8F0";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(0, packet.evaluate());
+        assert_eq!(0, packet.evaluate().unwrap());
     }
 
     // 9C0141080250320F1802104A08 produces 1, because 1 + 3 = 2 * 2.
     #[test]
     fn test_eval_example_8() {
         let hex = "9C0141080250320F1802104A08";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
-        assert_eq!(1, packet.evaluate());
+        assert_eq!(1, packet.evaluate().unwrap());
     }
 
     #[test]
     fn test_huge() {
         let hex = "E20D7880532D4E551A5791BD7B8C964C1548CB3EC1FCA41CC00C6D50024400C202A65C00C20257C008AF70024C00810039C00C3002D400A300258040F200D6040093002CC0084003FA52DB8134DE620EC01DECC4C8A5B55E204B6610189F87BDD3B30052C01493E2DC9F1724B3C1F8DC801E249E8D66C564715589BCCF08B23CA1A00039D35FD6AC5727801500260B8801F253D467BFF99C40182004223B4458D2600E42C82D07CC01D83F0521C180273D5C8EE802B29F7C9DA1DCACD1D802469FF57558D6A65372113005E4DB25CF8C0209B329D0D996C92605009A637D299AEF06622CE4F1D7560141A52BC6D91C73CD732153BF862F39BA49E6BA8C438C010E009AA6B75EF7EE53BBAC244933A48600B025AD7C074FEB901599A49808008398142013426BD06FA00D540010C87F0CA29880370E21D42294A6E3BCF0A080324A006824E3FCBE4A782E7F356A5006A587A56D3699CF2F4FD6DF60862600BF802F25B4E96BDD26049802333EB7DDB401795FC36BD26A860094E176006A0200FC4B8790B4001098A50A61748D2DEDDF4C6200F4B6FE1F1665BED44015ACC055802B23BD87C8EF61E600B4D6BAD5800AA4E5C8672E4E401D0CC89F802D298F6A317894C7B518BE4772013C2803710004261EC318B800084C7288509E56FD6430052482340128FB37286F9194EE3D31FA43BACAF2802B12A7B83E4017E4E755E801A2942A9FCE757093005A6D1F803561007A17C3B8EE0008442085D1E8C0109E3BC00CDE4BFED737A90DC97FDAE6F521B97B4619BE17CC01D94489E1C9623000F924A7C8C77EA61E6679F7398159DE7D84C015A0040670765D5A52D060200C92801CA8A531194E98DA3CCF8C8C017C00416703665A2141008CF34EF8019A080390962841C1007217C5587E60164F81C9A5CE0E4AA549223002E32BDCEA36B2E100A160008747D8B705C001098DB13A388803F1AE304600";
-        let packet = packet::parse_hex(hex);
+        let packet = packet::parse_hex(hex).unwrap();
 
         assert_eq!(979_u128, packet.versions().iter().map(|&v| v as u128).sum());
-        assert_eq!(277110354175_u128, packet.evaluate());
+        assert_eq!(277110354175_u128, packet.evaluate().unwrap());
+        assert_eq!(hex, packet::encode_hex(&*packet).unwrap());
+    }
+
+    #[test]
+    fn test_parse_hex_not_hex() {
+        let error = packet::parse_hex("D2FG28").err().unwrap();
+
+        assert_eq!((1, 4), (error.line(), error.column()));
+
+        // a character of more than one byte
+        let error = packet::parse_hex("D2Fé28").err().unwrap();
+        assert_eq!((1, 4), (error.line(), error.column()));
+        assert_eq!("`é` is not a hex digit", error.explanation());
+    }
+
+    #[test]
+    fn test_decode_bytes() {
+        let packet = packet::decode(&[0xD2, 0xFE, 0x28][..]).unwrap();
+
+        assert_eq!(vec![2021], packet.values());
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let examples = [
+            "D2FE28", "38006F45291200", "EE00D40C823060", "8A004A801A8002F478",
+            "620080001611562C8802118E34", "C0015000016115A2E0802F182340",
+            "A0016C880162017C3686B18A3D4780", "C200B40A82", "04005AC33890",
+            "880086C3E88112", "CE00C43D881120", "D8005AC2A8F0", "F600BC2D8F",
+            "9C005AC2F8F0", "9C0141080250320F1802104A08",
+        ];
+
+        for hex in examples {
+            let packet = packet::parse_hex(hex).unwrap();
+            assert_eq!(hex, packet::encode_hex(&*packet).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_encode_built() {
+        // the operator from `test_versions_example_1`
+        let packet = packet::Operator::new(1, packet::OpKind::LessThan, vec![packet::literal(6, 10), packet::literal(2, 20)])
+            .with_length_type(packet::LengthType::Bits);
+        let hex = packet::encode_hex(&packet).unwrap();
+
+        assert_eq!("38006F45291200", hex);
+
+        // counting the subpackets instead
+        let packet = packet::operator(1, packet::OpKind::LessThan, vec![packet::literal(6, 10), packet::literal(2, 20)]);
+        let decoded = packet::parse_hex(&packet::encode_hex(&*packet).unwrap()).unwrap();
+
+        assert_eq!(vec![1, 6, 2], decoded.versions());
+        assert_eq!(1, decoded.evaluate().unwrap());
+    }
+
+    #[test]
+    fn test_encode_expression() {
+        let expression = "(max(1,20,3)*((4+5)=9))";
+        let packet = packet::parse_expression(expression).unwrap();
+        let decoded = packet::parse_hex(&packet::encode_hex(&*packet).unwrap()).unwrap();
+
+        assert_eq!(expression, decoded.eval_string());
+        assert_eq!(20, decoded.evaluate().unwrap());
+    }
+
+    #[test]
+    fn test_no_value() {
+        // an equal to with a single subpacket
+        let error = Solver::parse("1E004408").err().unwrap();
+
+        assert_eq!("line 1, column 1: the transmission has no value: equal to takes 2 operands, not 1", error.to_string());
+    }
+
+    #[test]
+    fn test_parse_hex_truncated() {
+        let error = packet::parse_hex("D2FE2").err().unwrap();
+
+        assert_eq!((1, 5), (error.line(), error.column()));
     }
 }
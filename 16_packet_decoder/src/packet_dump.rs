@@ -0,0 +1,95 @@
+use std::fmt;
+use std::io::Read;
+use std::ops::Range;
+
+use crate::packet::{BitReader, DecodeError, Header, LengthType, OpKind};
+use crate::packet_parse::decode_traced;
+
+/// One packet of a transmission, and the bits it was decoded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpEntry {
+    /// How many operators the packet is inside of.
+    pub depth: usize,
+    /// The offsets of the packet's first bit and of the bit just past it.
+    pub bits: Range<usize>,
+    pub header: Header,
+    pub body: DumpBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DumpBody {
+    Literal { value: u128, groups: usize },
+    // `length` is in bits or in subpackets, depending on `length_type`
+    Operator { length_type: LengthType, length: u32 },
+}
+
+impl fmt::Display for DumpEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let range = format!("{}..{}", self.bits.start, self.bits.end);
+        write!(f, "{:<12}{:indent$}v{} ", range, "", self.header.version, indent = 2 * self.depth)?;
+
+        match &self.body {
+            DumpBody::Literal { value, groups } => {
+                write!(f, "literal (type {}) = {} in {} group{}", self.header.type_id, value, groups, plural(*groups))
+            },
+            DumpBody::Operator { length_type, length } => {
+                let kind = OpKind::from_type_id(self.header.type_id).unwrap();
+                write!(f, "{} (type {}), ", kind, self.header.type_id)?;
+                match length_type {
+                    LengthType::Bits => write!(f, "length type 0: {} bits of subpackets", length),
+                    LengthType::Count => write!(f, "length type 1: {} subpacket{}", length, plural(*length as usize)),
+                }
+            },
+        }
+    }
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 { "" } else { "s" }
+}
+
+/// Decode the packet at the reader's position, listing each packet in it
+/// before its subpackets.
+pub fn disassemble<R: Read>(bits: &mut BitReader<R>) -> Result<Vec<DumpEntry>, DecodeError> {
+    let mut entries = Vec::new();
+    decode_traced(bits, 0, &mut entries)?;
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packet_parse::hex_to_bytes;
+
+    fn dump(hex: &str) -> Vec<String> {
+        let bytes = hex_to_bytes(hex);
+        let mut bits = BitReader::with_limit(&bytes[..], hex.len() * 4);
+        disassemble(&mut bits).unwrap().iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(vec!["0..21       v6 literal (type 4) = 2021 in 3 groups"], dump("D2FE28"));
+    }
+
+    #[test]
+    fn test_operator() {
+        let expected = vec![
+            "0..49       v1 less than (type 6), length type 0: 27 bits of subpackets",
+            "22..33        v6 literal (type 4) = 10 in 1 group",
+            "33..49        v2 literal (type 4) = 20 in 2 groups",
+        ];
+
+        assert_eq!(expected, dump("38006F45291200"));
+    }
+
+    #[test]
+    fn test_nested() {
+        let bytes = hex_to_bytes("8A004A801A8002F478");
+        let entries = disassemble(&mut BitReader::new(&bytes[..])).unwrap();
+
+        assert_eq!(vec![0, 1, 2, 3], entries.iter().map(|entry| entry.depth).collect::<Vec<_>>());
+        assert_eq!(0..69, entries[0].bits);
+        assert_eq!(58..69, entries[3].bits);
+    }
+}
@@ -0,0 +1,290 @@
+use std::error::Error;
+use std::fmt;
+
+use common::ParseError;
+
+use crate::packet::{literal, operator, OpKind, Packet};
+
+/// A packet that cannot be encoded because its subpackets do not fit in the
+/// length field it was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    // nothing could decode to it, or be written as an expression
+    NoSubpackets(OpKind),
+    // a count of subpackets is only 11 bits
+    TooManySubpackets(usize),
+    // a length of subpackets is only 15 bits
+    SubpacketsTooLong(usize),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSubpackets(kind) => write!(f, "{} operators need subpackets", kind),
+            Self::TooManySubpackets(count) => write!(f, "{} subpackets do not fit in an 11 bit count", count),
+            Self::SubpacketsTooLong(bits) => write!(f, "{} bits of subpackets do not fit in a 15 bit length", bits),
+        }
+    }
+}
+
+impl Error for EncodeError {}
+
+/// Collects fields into bytes, most significant bit first.
+#[derive(Debug, Clone, Default)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many bits have been written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Write the low `bits` bits of `value`.
+    pub fn write(&mut self, value: u32, bits: u32) {
+        assert!(bits <= 32, "fields are at most 32 bits");
+        for i in (0..bits).rev() {
+            self.push(value >> i & 1 == 1);
+        }
+    }
+
+    /// Write everything written to `other`.
+    pub fn append(&mut self, other: &BitWriter) {
+        for i in 0..other.len {
+            self.push(other.bytes[i / 8] >> (7 - i % 8) & 1 == 1);
+        }
+    }
+
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 1 << (7 - self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    /// The bits written, padded with 0's to a whole number of bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The bits written as hex, padded with 0's to a whole number of bytes.
+    pub fn to_hex(&self) -> String {
+        self.bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+    }
+}
+
+/// Encode a packet as a hex transmission.
+pub fn encode_hex(packet: &dyn Packet) -> Result<String, EncodeError> {
+    let mut bits = BitWriter::new();
+    packet.encode(&mut bits)?;
+    Ok(bits.to_hex())
+}
+
+/// Build a packet from the infix syntax of `Packet::eval_string`, like
+/// `(min(7,8)*(5<15))`. Every packet gets version 0.
+///
+/// A parenthesized group joins its packets with one of `+`, `*`, `<`, `>` or
+/// `=`, and the comparisons take exactly two. A group of one is a sum.
+pub fn parse_expression(input: &str) -> Result<Box<dyn Packet>, ParseError> {
+    let mut expression = Expression { input, at: 0 };
+    let packet = expression.packet()?;
+
+    expression.skip_space();
+    if expression.at < input.len() {
+        return Err(expression.error("expected the end of the expression"));
+    }
+
+    Ok(packet)
+}
+
+struct Expression<'a> {
+    input: &'a str,
+    // byte offset of the next character to parse
+    at: usize,
+}
+
+impl<'a> Expression<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.at..]
+    }
+
+    fn error(&self, explanation: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, self.rest(), explanation)
+    }
+
+    fn skip_space(&mut self) {
+        let rest = self.rest();
+        self.at += rest.len() - rest.trim_start().len();
+    }
+
+    // consume `token` if it comes next
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_space();
+        let found = self.rest().starts_with(token);
+        if found {
+            self.at += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", token)))
+        }
+    }
+
+    fn packet(&mut self) -> Result<Box<dyn Packet>, ParseError> {
+        if self.eat("min") {
+            self.call(OpKind::Minimum)
+        } else if self.eat("max") {
+            self.call(OpKind::Maximum)
+        } else if self.eat("(") {
+            self.group()
+        } else {
+            self.number()
+        }
+    }
+
+    // the arguments of `min` or `max`
+    fn call(&mut self, kind: OpKind) -> Result<Box<dyn Packet>, ParseError> {
+        self.expect("(")?;
+        let mut subpackets = vec![self.packet()?];
+        while self.eat(",") {
+            subpackets.push(self.packet()?);
+        }
+        self.expect(")")?;
+
+        Ok(operator(0, kind, subpackets))
+    }
+
+    // a parenthesized group, after its `(`
+    fn group(&mut self) -> Result<Box<dyn Packet>, ParseError> {
+        let start = self.at - 1;
+        let mut subpackets = vec![self.packet()?];
+        let mut op = None;
+
+        loop {
+            let next = ["+", "*", "<", ">", "="].into_iter().find(|op| self.eat(op));
+            let next = match next {
+                Some(next) => next,
+                None => break,
+            };
+            if op.is_some_and(|op| op != next) {
+                self.at -= 1;
+                return Err(self.error("operators cannot be mixed within parentheses"));
+            }
+            op = Some(next);
+            subpackets.push(self.packet()?);
+        }
+        self.expect(")")?;
+
+        let kind = match op {
+            None | Some("+") => OpKind::Sum,
+            Some("*") => OpKind::Product,
+            Some(">") => OpKind::GreaterThan,
+            Some("<") => OpKind::LessThan,
+            _ => OpKind::EqualTo,
+        };
+        if kind.is_comparison() && subpackets.len() != 2 {
+            let group = &self.input[start..];
+            return Err(ParseError::at(self.input, group, "comparisons take exactly two packets"));
+        }
+
+        Ok(operator(0, kind, subpackets))
+    }
+
+    fn number(&mut self) -> Result<Box<dyn Packet>, ParseError> {
+        let rest = self.rest();
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(self.error("expected a number, `(`, `min` or `max`"));
+        }
+
+        let value = common::parse_at(self.input, &rest[..digits])?;
+        self.at += digits;
+        Ok(literal(0, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packet::{LengthType, Operator};
+
+    #[test]
+    fn test_bit_writer() {
+        let mut bits = BitWriter::new();
+        bits.write(0b101, 3);
+        bits.write(0b0_1100_0011, 9);
+
+        let mut more = BitWriter::new();
+        more.write(0b01, 2);
+        bits.append(&more);
+
+        assert_eq!(14, bits.len());
+        assert_eq!("AC34", bits.to_hex());
+        assert_eq!(vec![0b1010_1100, 0b0011_0100], bits.into_bytes());
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let packet = parse_expression("(min(7, 8) * (5<15))").unwrap();
+
+        assert_eq!("(min(7,8)*(5<15))", packet.eval_string());
+        assert_eq!(7, packet.evaluate().unwrap());
+        assert_eq!("(3)", parse_expression("(3)").unwrap().eval_string());
+    }
+
+    #[test]
+    fn test_parse_expression_errors() {
+        let column = |input| parse_expression(input).err().unwrap().column();
+
+        assert_eq!(5, column("(1+2*3)"));
+        assert_eq!(1, column("(1<2<3)"));
+        assert_eq!(5, column("min(,1)"));
+        assert_eq!(6, column("(1+2)3"));
+        assert_eq!(5, column("(1+2"));
+    }
+
+    #[test]
+    fn test_empty_operator() {
+        assert_eq!(Err(EncodeError::NoSubpackets(OpKind::Sum)), encode_hex(&*operator(0, OpKind::Sum, Vec::new())));
+        assert!(parse_expression("()").is_err());
+
+        // the smallest group there is survives the round trip
+        let hex = encode_hex(&*parse_expression("(3)").unwrap()).unwrap();
+        let decoded = crate::packet::parse_hex(&hex).unwrap();
+        assert_eq!("(3)", decoded.eval_string());
+        assert_eq!("(3)", parse_expression(&decoded.eval_string()).unwrap().eval_string());
+    }
+
+    #[test]
+    fn test_length_does_not_fit() {
+        let literals = |n| (0..n).map(|_| literal(0, 0)).collect();
+
+        // too many to count, so their length is given in bits instead
+        assert!(encode_hex(&*operator(0, OpKind::Sum, literals(2048))).is_ok());
+
+        let counted = Operator::new(0, OpKind::Sum, literals(2048)).with_length_type(LengthType::Count);
+        assert_eq!(Err(EncodeError::TooManySubpackets(2048)), encode_hex(&counted));
+
+        // each literal is 11 bits
+        let packet = operator(0, OpKind::Sum, literals(3000));
+        assert_eq!(Err(EncodeError::SubpacketsTooLong(33000)), encode_hex(&*packet));
+    }
+}
@@ -0,0 +1,130 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::packet::OpKind;
+
+/// Why a packet has no value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    EmptyOperands(OpKind),
+    // comparisons take exactly two operands
+    WrongArity { kind: OpKind, operands: usize },
+    UnknownTypeId(u8),
+    Overflow(OpKind),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyOperands(kind) => write!(f, "{} has no operands", kind),
+            Self::WrongArity { kind, operands } => write!(f, "{} takes 2 operands, not {}", kind, operands),
+            Self::UnknownTypeId(type_id) => write!(f, "type ID {} is not an operator", type_id),
+            Self::Overflow(kind) => write!(f, "{} overflows", kind),
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+/// A number that packets can evaluate to.
+pub trait Value: Ord + Sized {
+    fn from_bool(b: bool) -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+impl Value for u128 {
+    fn from_bool(b: bool) -> Self {
+        b as u128
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u128::checked_add(self, other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        u128::checked_mul(self, other)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Value for num_bigint::BigUint {
+    fn from_bool(b: bool) -> Self {
+        (b as u8).into()
+    }
+
+    // arbitrary precision never overflows
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+/// Apply the operator with `type_id` to the values of its subpackets.
+pub fn operate<V: Value>(type_id: u8, operands: Vec<V>) -> Result<V, EvalError> {
+    let kind = OpKind::from_type_id(type_id).ok_or(EvalError::UnknownTypeId(type_id))?;
+    if operands.is_empty() {
+        return Err(EvalError::EmptyOperands(kind));
+    }
+    if kind.is_comparison() && operands.len() != 2 {
+        return Err(EvalError::WrongArity { kind, operands: operands.len() });
+    }
+
+    let mut operands = operands.into_iter();
+    let first = operands.next().unwrap();
+    let overflow = || EvalError::Overflow(kind);
+
+    match kind {
+        OpKind::Sum => operands.try_fold(first, |a, b| a.checked_add(b).ok_or_else(overflow)),
+        OpKind::Product => operands.try_fold(first, |a, b| a.checked_mul(b).ok_or_else(overflow)),
+        OpKind::Minimum => Ok(operands.fold(first, V::min)),
+        OpKind::Maximum => Ok(operands.fold(first, V::max)),
+        OpKind::GreaterThan => Ok(V::from_bool(first > operands.next().unwrap())),
+        OpKind::LessThan => Ok(V::from_bool(first < operands.next().unwrap())),
+        OpKind::EqualTo => Ok(V::from_bool(first == operands.next().unwrap())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packet::{literal, operator, parse_expression};
+
+    #[test]
+    fn test_operate() {
+        assert_eq!(Ok(6), operate(0, vec![1_u128, 2, 3]));
+        assert_eq!(Ok(1), operate(6, vec![1_u128, 2]));
+        assert_eq!(Err(EvalError::EmptyOperands(OpKind::Maximum)), operate::<u128>(3, vec![]));
+        assert_eq!(Err(EvalError::UnknownTypeId(4)), operate(4, vec![1_u128]));
+    }
+
+    #[test]
+    fn test_wrong_arity() {
+        let packet = operator(0, OpKind::EqualTo, vec![literal(0, 1), literal(0, 1), literal(0, 1)]);
+        let error = packet.evaluate().unwrap_err();
+
+        assert_eq!(EvalError::WrongArity { kind: OpKind::EqualTo, operands: 3 }, error);
+        assert_eq!("equal to takes 2 operands, not 3", error.to_string());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let packet = parse_expression("(18446744073709551616*18446744073709551616)").unwrap();
+
+        assert_eq!(Err(EvalError::Overflow(OpKind::Product)), packet.evaluate());
+        assert_eq!(Ok(u128::MAX), parse_expression("(340282366920938463463374607431768211454+1)").unwrap().evaluate());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_big() {
+        let packet = parse_expression("((18446744073709551616*18446744073709551616)+1)").unwrap();
+
+        assert_eq!("340282366920938463463374607431768211457", packet.evaluate_big().unwrap().to_string());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::packet::{Header, LengthType, Literal, OpKind, Operator, Packet};
+use crate::packet_parse::LITERAL_TYPE_ID;
+
+/// A packet tree that can be taken apart, unlike a `Box<dyn Packet>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketNode {
+    Literal {
+        version: u8,
+        value: u128,
+    },
+    Operator {
+        version: u8,
+        kind: OpKind,
+        length_type: LengthType,
+        children: Vec<PacketNode>,
+    },
+}
+
+/// Walks a packet tree, seeing each node before its children.
+pub trait Visitor {
+    /// Called on a node before any of its children. The root is at depth 0.
+    fn enter(&mut self, node: &PacketNode, depth: usize);
+
+    /// Called on a node after all of its children.
+    fn leave(&mut self, _node: &PacketNode, _depth: usize) {}
+}
+
+impl PacketNode {
+    pub fn header(&self) -> Header {
+        match self {
+            PacketNode::Literal { version, .. } => Header { version: *version, type_id: LITERAL_TYPE_ID },
+            PacketNode::Operator { version, kind, .. } => Header { version: *version, type_id: kind.type_id() },
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        self.header().version
+    }
+
+    /// The subpackets of an operator; a literal has none.
+    pub fn children(&self) -> &[PacketNode] {
+        match self {
+            PacketNode::Literal { .. } => &[],
+            PacketNode::Operator { children, .. } => children,
+        }
+    }
+
+    /// Show every node of the tree to `visitor`.
+    pub fn visit(&self, visitor: &mut impl Visitor) {
+        self.visit_at(visitor, 0);
+    }
+
+    fn visit_at(&self, visitor: &mut impl Visitor, depth: usize) {
+        visitor.enter(self, depth);
+        for child in self.children() {
+            child.visit_at(visitor, depth + 1);
+        }
+        visitor.leave(self, depth);
+    }
+
+    /// Combine the tree from the leaves up: `f` gets each node along with
+    /// what its children folded to.
+    pub fn fold<T>(&self, mut f: impl FnMut(&PacketNode, Vec<T>) -> T) -> T {
+        self.fold_with(&mut f)
+    }
+
+    fn fold_with<T>(&self, f: &mut impl FnMut(&PacketNode, Vec<T>) -> T) -> T {
+        let children = self.children().iter().map(|child| child.fold_with(f)).collect();
+        f(self, children)
+    }
+
+    /// Every node of the tree, each before its children.
+    pub fn iter(&self) -> impl Iterator<Item = &PacketNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children().iter().rev());
+            Some(node)
+        })
+    }
+}
+
+impl From<&Literal> for PacketNode {
+    fn from(literal: &Literal) -> Self {
+        PacketNode::Literal { version: literal.version(), value: literal.value() }
+    }
+}
+
+impl From<&Operator> for PacketNode {
+    fn from(operator: &Operator) -> Self {
+        PacketNode::Operator {
+            version: operator.version(),
+            kind: operator.kind(),
+            length_type: operator.length_type(),
+            children: operator.subpackets().iter().map(|subpacket| subpacket.to_node()).collect(),
+        }
+    }
+}
+
+impl From<&dyn Packet> for PacketNode {
+    fn from(packet: &dyn Packet) -> Self {
+        packet.to_node()
+    }
+}
+
+impl From<PacketNode> for Box<dyn Packet> {
+    fn from(node: PacketNode) -> Self {
+        match node {
+            PacketNode::Literal { version, value } => Box::new(Literal::new(version, value)),
+            PacketNode::Operator { version, kind, length_type, children } => {
+                let subpackets = children.into_iter().map(Box::from).collect();
+                Box::new(Operator::new(version, kind, subpackets).with_length_type(length_type))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Counter;
+
+    use crate::packet::parse_hex;
+
+    // an operator (version 4) holding an operator (version 1) holding an
+    // operator (version 5) holding a literal (version 6)
+    const NESTED: &str = "8A004A801A8002F478";
+
+    fn node(hex: &str) -> PacketNode {
+        PacketNode::from(&*parse_hex(hex).unwrap())
+    }
+
+    #[test]
+    fn test_from_packet() {
+        let expected = PacketNode::Operator {
+            version: 1,
+            kind: OpKind::LessThan,
+            length_type: LengthType::Bits,
+            children: vec![
+                PacketNode::Literal { version: 6, value: 10 },
+                PacketNode::Literal { version: 2, value: 20 },
+            ],
+        };
+
+        assert_eq!(expected, node("38006F45291200"));
+        assert_eq!(Header { version: 1, type_id: 6 }, expected.header());
+    }
+
+    #[test]
+    fn test_back_to_packet() {
+        let packet: Box<dyn Packet> = node("9C0141080250320F1802104A08").into();
+
+        assert_eq!("((1+3)=(2*2))", packet.eval_string());
+        assert_eq!("9C0141080250320F1802104A08", crate::packet::encode_hex(&*packet).unwrap());
+    }
+
+    #[test]
+    fn test_visitor() {
+        // counts nodes, and the deepest any node is
+        #[derive(Default)]
+        struct Shape {
+            nodes: usize,
+            depth: usize,
+        }
+
+        impl Visitor for Shape {
+            fn enter(&mut self, _: &PacketNode, depth: usize) {
+                self.nodes += 1;
+                self.depth = self.depth.max(depth);
+            }
+        }
+
+        let mut shape = Shape::default();
+        node(NESTED).visit(&mut shape);
+
+        assert_eq!((4, 3), (shape.nodes, shape.depth));
+    }
+
+    #[test]
+    fn test_fold() {
+        let depth = |node: &PacketNode| node.fold(|_, children: Vec<usize>| {
+            children.into_iter().max().map_or(1, |deepest| deepest + 1)
+        });
+
+        assert_eq!(4, depth(&node(NESTED)));
+        assert_eq!(1, depth(&node("D2FE28")));
+    }
+
+    #[test]
+    fn test_version_histogram() {
+        let packet = node("A0016C880162017C3686B18A3D4780");
+        let versions: Counter<u8> = packet.iter().map(|node| node.version()).collect();
+
+        assert_eq!(31, versions.iter().map(|(&version, &count)| version as u32 * count).sum::<u32>());
+        assert_eq!(8, versions.total());
+        // in the same order as `Packet::versions`
+        let expected = parse_hex("A0016C880162017C3686B18A3D4780").unwrap().versions();
+        assert_eq!(expected, packet.iter().map(|node| node.version()).collect::<Vec<_>>());
+    }
+}
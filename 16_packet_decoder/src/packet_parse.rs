@@ -1,223 +1,212 @@
-use literal_body::LiteralBody;
-
-const VERSION: usize = 3;
-const TYPE_ID: usize = 3;
-const LEN_ID: usize = 1;
-const LEN_BITS: usize = 15;
-const LEN_PACKETS: usize = 11;
-const HEADER: usize = VERSION + TYPE_ID;
-
-/// Get the version of a packet.
-pub fn version(packet: &str) -> u8 {
-    assert!(packet.len() >= VERSION, "packet too short");
-    u8::from_str_radix(&packet[..VERSION], 2).unwrap()
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+use crate::packet::{Header, OpKind, Packet};
+use crate::packet_dump::{DumpBody, DumpEntry};
+use crate::packet_types::literal::Literal;
+use crate::packet_types::operator::{LengthType, Operator};
+
+pub const VERSION: u32 = 3;
+pub const TYPE_ID: u32 = 3;
+pub const GROUP: u32 = 5;
+pub const LEN_ID: u32 = 1;
+pub const LEN_BITS: u32 = 15;
+pub const LEN_PACKETS: u32 = 11;
+
+pub const LITERAL_TYPE_ID: u8 = 4;
+
+/// Where and why decoding a transmission failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    bit: usize,
+    explanation: String,
 }
 
-/// Get the type ID of a packet.
-pub fn type_id(packet: &str) -> u8 {
-    assert!(packet.len() >= HEADER, "packet too short");
-    u8::from_str_radix(&packet[VERSION..HEADER], 2).unwrap()
-}
+impl DecodeError {
+    pub fn new(bit: usize, explanation: impl Into<String>) -> Self {
+        Self { bit, explanation: explanation.into() }
+    }
+
+    /// The offset of the field that could not be decoded, in bits from the
+    /// start of the transmission.
+    pub fn bit(&self) -> usize {
+        self.bit
+    }
 
-/// Get the header of a literal packet.
-pub fn _literal_header(packet: &str) -> &str {
-    assert!(packet.len() >= HEADER, "packet too short");
-    &packet[..HEADER]
+    pub fn explanation(&self) -> &str {
+        &self.explanation
+    }
 }
 
-/// Get the body of a literal packet, including any extra trailing 0's.
-pub fn literal_body(packet: &str) -> &str {
-    assert!(packet.len() >= HEADER, "packet too short");
-    &packet[HEADER..]
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at bit {}", self.explanation, self.bit)
+    }
 }
 
-/// Get the parsed value from a literal packet.
-pub fn literal_value(packet_fragment: &str) -> String {
-    LiteralBody::from(packet_fragment).value()
+impl Error for DecodeError {}
+
+/// Reads fields of up to 32 bits, most significant bit first, from any byte
+/// source.
+///
+/// Bytes are pulled from the source one at a time as fields need them, so
+/// wrap unbuffered sources like files in a `BufReader`.
+pub struct BitReader<R> {
+    inner: R,
+    // bits pulled from `inner` but not yet read, in the low `buffered` bits
+    buffer: u64,
+    buffered: u32,
+    position: usize,
+    limit: Option<usize>,
 }
 
-const HEADER_LENID: usize = HEADER + LEN_ID;
+impl<R: Read> BitReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, buffer: 0, buffered: 0, position: 0, limit: None }
+    }
 
-/// Get the length type ID of an operator packet.
-pub fn len_type_id(packet: &str) -> u8 {
-    assert!(packet.len() >= HEADER_LENID, "packet too short");
-    packet.chars().nth(HEADER_LENID - 1).unwrap().to_digit(2).unwrap().try_into().unwrap()
-}
+    /// A reader that ends after `bits` bits, for sources like hex whose last
+    /// byte may only be partly used.
+    pub fn with_limit(inner: R, bits: usize) -> Self {
+        Self { limit: Some(bits), ..Self::new(inner) }
+    }
 
-/// Get the subpacket length field of an operator packet, either a bit count or 
-/// a packet count depending on the packet's length type ID.
-pub fn subpackets_len(packet: &str) -> usize {
-    match len_type_id(packet) {
-        0 => subpackets_len_bits(packet),
-        1 => subpackets_len_packets(packet),
-        _ => panic!("invalid length id; should not get here"),
+    /// How many bits have been read.
+    pub fn position(&self) -> usize {
+        self.position
     }
-}
 
-/// Get the subpacket length of an operator packet as a bit count.
-pub fn subpackets_len_bits(packet: &str) -> usize {
-    assert!(packet.len() >= HEADER + LEN_BITS, "packet too short");
-    usize::from_str_radix(&packet[HEADER..HEADER_LENID + LEN_BITS], 2).unwrap()
-}
+    /// Read a `bits` wide field. On failure the error points at the start of
+    /// the field and nothing is read.
+    pub fn read(&mut self, bits: u32) -> Result<u32, DecodeError> {
+        assert!(bits <= 32, "fields are at most 32 bits");
 
-/// Get the subpacket length of an operator packet as a packet count.
-pub fn subpackets_len_packets(packet: &str) -> usize {
-    assert!(packet.len() >= HEADER_LENID + LEN_PACKETS, "packet too short");
-    usize::from_str_radix(&packet[HEADER_LENID..HEADER_LENID + LEN_PACKETS], 2).unwrap()
-}
+        if self.limit.is_some_and(|limit| self.position + bits as usize > limit) {
+            return Err(DecodeError::new(self.position, "packet ends early"));
+        }
 
-/// Get the header of an operator packet, including length metadata.
-pub fn operator_header(packet: &str) -> &str {
-    let len_type_id = match len_type_id(packet) {
-        0 => LEN_BITS,
-        1 => LEN_PACKETS,
-        _ => panic!("invalid length id; should not get here"),
-    };
+        while self.buffered < bits {
+            let mut byte = [0];
+            match self.inner.read(&mut byte) {
+                Ok(0) => return Err(DecodeError::new(self.position, "packet ends early")),
+                Ok(_) => {
+                    self.buffer = self.buffer << 8 | byte[0] as u64;
+                    self.buffered += 8;
+                },
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(DecodeError::new(self.position, format!("could not read: {}", e))),
+            }
+        }
 
-    &packet[..HEADER_LENID + len_type_id]
-}
+        self.buffered -= bits;
+        self.position += bits as usize;
+        let field = (self.buffer >> self.buffered) & ((1 << bits) - 1);
+        self.buffer &= (1 << self.buffered) - 1;
 
-/// Get the body of an operator packet, excluding length metadata.
-pub fn subpackets_body(packet: &str) -> &str {
-    match len_type_id(packet) {
-        0 => &packet[HEADER_LENID + LEN_BITS..],
-        1 => &packet[HEADER_LENID + LEN_PACKETS..],
-        _ => panic!("invalid length id; should not get here"),
+        Ok(field as u32)
     }
 }
 
-/// Find all the subpackets of the packet that leads a fragment.
-pub fn find_subpackets(packet_fragment: &str) -> Vec<&str> {
-    if type_id(packet_fragment) == 4 {
-        return vec![];
-    }
-
-    match len_type_id(packet_fragment) {
-        0 => find_subpackets_bits(packet_fragment),
-        1 => find_subpackets_count(packet_fragment),
-        _ => panic!("should never get here"),
-    }
+/// Decode the packet at the reader's position, leaving the reader just past
+/// its end.
+pub fn decode_packet<R: Read>(bits: &mut BitReader<R>) -> Result<Box<dyn Packet>, DecodeError> {
+    decode_traced(bits, 0, &mut Vec::new())
 }
 
-/// Find the first packet of any kind that leads a fragment.
-fn lead_packet(packet_fragment: &str) -> &str {
-    match type_id(packet_fragment) {
-        4 => {
-            let len = find_literal(packet_fragment).len();
-            &packet_fragment[..len]
-        },
-        _ => {
-            let len = find_operator(packet_fragment).len();
-            &packet_fragment[..len]
+/// Decode a packet `depth` operators deep, adding an entry to `trace` for it
+/// and for each of its subpackets.
+pub fn decode_traced<R: Read>(
+    bits: &mut BitReader<R>,
+    depth: usize,
+    trace: &mut Vec<DumpEntry>,
+) -> Result<Box<dyn Packet>, DecodeError> {
+    let start = bits.position();
+    let version = bits.read(VERSION)? as u8;
+    let type_id = bits.read(TYPE_ID)? as u8;
+    let header = Header { version, type_id };
+
+    if type_id == LITERAL_TYPE_ID {
+        let groups_start = bits.position();
+        let mut value: u128 = 0;
+        let mut groups = 0;
+
+        // groups continue until one leads with a 0
+        loop {
+            let group = bits.read(GROUP)?;
+            if value.leading_zeros() < 4 {
+                return Err(DecodeError::new(groups_start, "literal does not fit in 128 bits"));
+            }
+            value = value << 4 | (group & 0b1111) as u128;
+            groups += 1;
+            if group & 0b10000 == 0 {
+                let body = DumpBody::Literal { value, groups };
+                trace.push(DumpEntry { depth, bits: start..bits.position(), header, body });
+                return Ok(Box::new(Literal::new(version, value)));
+            }
         }
     }
-}
-
-/// Find first literal packet that leads a fragment.
-fn find_literal(packet_fragment: &str) -> &str {
-    assert!(type_id(packet_fragment) == 4, "not a literal");
-    let body = LiteralBody::from(packet_fragment).body();
-    &packet_fragment[..HEADER + body.len()]
-}
-
-/// Find the first operator packet that leads a fragment.
-fn find_operator(packet_fragment: &str) -> &str {
-    let header_len = operator_header(packet_fragment).len();
-    let body_len: usize = find_subpackets(packet_fragment).iter().map(|sp| sp.len()).sum();
-
-    &packet_fragment[..header_len + body_len]
-}
 
-/// Find the subpackets of an operator packet where the length is given as a 
-/// bit count.
-fn find_subpackets_bits(packet_fragment: &str) -> Vec<&str> {
-    let subpackets_len = subpackets_len(packet_fragment);
-    let subpackets_body = subpackets_body(packet_fragment);
-
-    let mut result = Vec::new();
-    let mut bits = subpackets_len;
-    let mut remaining = subpackets_body;
-
-    while bits > 0 {
-        let next_packet = lead_packet(remaining);
-        assert!(next_packet.len() <= bits, "subpackets not parseable in {} bits", subpackets_len);
-        bits -= next_packet.len();
-        remaining = &remaining[next_packet.len()..];
-        result.push(next_packet);
+    // the operator's entry comes before its subpackets', but its end is only
+    // known after them
+    let entry = trace.len();
+    let length_id = bits.read(LEN_ID)?;
+    let length = bits.read(if length_id == 0 { LEN_BITS } else { LEN_PACKETS })?;
+    let length_type = if length_id == 0 { LengthType::Bits } else { LengthType::Count };
+    let body = DumpBody::Operator { length_type, length };
+    trace.push(DumpEntry { depth, bits: start..start, header, body });
+
+    let mut subpackets = Vec::new();
+    match length_type {
+        LengthType::Bits => {
+            let end = bits.position() + length as usize;
+            while bits.position() < end {
+                subpackets.push(decode_traced(bits, depth + 1, trace)?);
+            }
+            if bits.position() > end {
+                return Err(DecodeError::new(end, "subpackets overrun their length"));
+            }
+        },
+        LengthType::Count => {
+            for _ in 0..length {
+                subpackets.push(decode_traced(bits, depth + 1, trace)?);
+            }
+        },
     }
+    trace[entry].bits.end = bits.position();
 
-    result
+    // keep the length type, so that the packet encodes as it was received
+    let kind = OpKind::from_type_id(type_id).unwrap();
+    let operator = Operator::new(version, kind, subpackets).with_length_type(length_type);
+    Ok(Box::new(operator))
 }
 
-/// Find the subpackets of an operator packet where the length is given as a 
-/// packet count.
-fn find_subpackets_count(packet_fragment: &str) -> Vec<&str> {
-    let subpackets_len = subpackets_len(packet_fragment);
-    let subpackets_body = subpackets_body(packet_fragment);
-
-    let mut result = Vec::new();
-    let mut count = subpackets_len;
-    let mut remaining = subpackets_body;
-    
-    while count > 0 {
-        let next_packet = lead_packet(remaining);
-        count -= 1;
-        remaining = &remaining[next_packet.len()..];
-        result.push(next_packet);
-    }
+/// Pack hex digits into bytes, padding the last with 0's if the number of
+/// digits is odd. Panics on anything that is not a hex digit.
+pub fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    pack(hex.chars().map(|c| c.to_digit(16).expect("not a hex digit") as u8), 4)
+}
 
-    result
+/// Pack a string of '0's and '1's into bytes, padding the last with 0's.
+/// Panics on anything else.
+pub fn bin_to_bytes(bin: &str) -> Vec<u8> {
+    pack(bin.chars().map(|c| c.to_digit(2).expect("not a binary digit") as u8), 1)
 }
 
-mod literal_body {
-    use super::{literal_body};
-    const CHUNK: usize = 5;
+// pack `width` bit digits into bytes, most significant first
+fn pack(digits: impl Iterator<Item = u8>, width: u32) -> Vec<u8> {
+    let per_byte = (8 / width) as usize;
+    let mut bytes = Vec::new();
 
-    pub struct LiteralBody<'a> {
-        binary: &'a str,
-        i: usize,
-        complete: bool,
-    }
-    
-    impl<'a> LiteralBody<'a> {
-        pub fn from(packet: &str) -> LiteralBody  {
-            LiteralBody {
-                binary: literal_body(packet),
-                i: 0,
-                complete: false,
-            }
-        }
-    
-        pub fn body(self) -> String {
-            self.collect::<String>()
-        }
-    
-        pub fn value(self) -> String {
-            self.map(|chunk| &chunk[1..]).collect::<String>()
-        }
-    }
-    
-    impl<'a> Iterator for LiteralBody<'a> {
-        type Item = &'a str;
-    
-        fn next(&mut self) -> Option<Self::Item> {
-            if self.complete {
-                None
-            } else if self.i + CHUNK > self.binary.len() {
-                self.complete = true;
-                None
-            } else {
-                let chunk = &self.binary[self.i..self.i+CHUNK];
-                let lead = chunk.chars().next().unwrap().to_digit(2).unwrap();
-                
-                if lead == 0 {
-                    self.complete = true;
-                }
-                self.i += CHUNK;
-                Some(chunk)
-            }
+    for (i, digit) in digits.enumerate() {
+        if i % per_byte == 0 {
+            bytes.push(0);
         }
+        let shift = 8 - width * (i % per_byte + 1) as u32;
+        *bytes.last_mut().unwrap() |= digit << shift;
     }
+
+    bytes
 }
 
 #[cfg(test)]
@@ -232,7 +221,7 @@ mod tests {
     // ] 0000000
     const OP_BITS: &str = "00111000000000000110111101000101001010010001001000000000";
     // { 111 011 1 00000000011 } [
-    //     { 010 100 } [ {0 0001} ] 
+    //     { 010 100 } [ {0 0001} ]
     //     { 100 100 } [ {0 0010} ]
     //     { 001 100 } [ {0 0011} ]
     // ] 00000
@@ -246,127 +235,136 @@ mod tests {
     // ] 000
     const NESTED: &str = "100010100000000001001010100000000001101010000000000000101111010001111000";
 
+    fn reader(bin: &str) -> BitReader<io::Cursor<Vec<u8>>> {
+        BitReader::with_limit(io::Cursor::new(bin_to_bytes(bin)), bin.len())
+    }
+
     #[test]
     fn test_version() {
-        let bin = LITERAL;
-        let version = version(bin);
+        let mut bits = reader(LITERAL);
         let expected = 6;
 
-        assert_eq!(expected, version);
+        assert_eq!(expected, bits.read(VERSION).unwrap());
     }
 
     #[test]
     fn test_typeid() {
-        let bin = LITERAL;
-        let type_id = type_id(bin);
-        let expected = 4;
-
-        assert_eq!(expected, type_id);
-    }
-
-    #[test]
-    fn test_literal_body() {
-        let bin = LITERAL;
-        let body = literal_body(bin);
-        let expected = "101111111000101000";
+        let mut bits = reader(LITERAL);
+        bits.read(VERSION).unwrap();
+        let expected = LITERAL_TYPE_ID as u32;
 
-        assert_eq!(expected, body);
+        assert_eq!(expected, bits.read(TYPE_ID).unwrap());
     }
 
     #[test]
     fn test_literal_value() {
-        let bin = LITERAL;
-        let body = literal_value(bin);
-        let expected = "011111100101";
+        let mut bits = reader(LITERAL);
+        let literal = decode_packet(&mut bits).unwrap();
 
-        assert_eq!(expected, body);
+        assert_eq!(vec![2021], literal.values());
+        // the trailing 0's are not part of the packet
+        assert_eq!(21, bits.position());
     }
 
     #[test]
-    fn test_operator_bits_typeid() {
-        let expected = 0;
+    fn test_operator_bits_len() {
+        let mut bits = reader(OP_BITS);
+        bits.read(VERSION + TYPE_ID).unwrap();
 
-        assert_eq!(expected, len_type_id(OP_BITS));
+        assert_eq!(0, bits.read(LEN_ID).unwrap());
+        assert_eq!(27, bits.read(LEN_BITS).unwrap());
     }
 
     #[test]
-    fn test_operator_count_typeid() {
-        let expected = 1;
+    fn test_operator_count_len() {
+        let mut bits = reader(OP_COUNT);
+        bits.read(VERSION + TYPE_ID).unwrap();
 
-        assert_eq!(expected, len_type_id(OP_COUNT));
+        assert_eq!(1, bits.read(LEN_ID).unwrap());
+        assert_eq!(3, bits.read(LEN_PACKETS).unwrap());
     }
 
     #[test]
-    fn test_operator_bits_len() {
-        let expected = 27;
+    fn test_subpackets_bits() {
+        let mut bits = reader(OP_BITS);
+        let operator = decode_packet(&mut bits).unwrap();
 
-        assert_eq!(expected, subpackets_len(OP_BITS));
+        assert_eq!(vec![10, 20], operator.values());
+        assert_eq!(49, bits.position());
     }
 
     #[test]
-    fn test_operator_count_len() {
-        let expected = 3;
+    fn test_subpackets_count() {
+        let mut bits = reader(OP_COUNT);
+        let operator = decode_packet(&mut bits).unwrap();
 
-        assert_eq!(expected, subpackets_len(OP_COUNT));
+        assert_eq!(vec![1, 2, 3], operator.values());
+        assert_eq!(51, bits.position());
     }
 
     #[test]
-    fn test_operator_bits_body() {
-        let expected = "1101000101001010010001001000000000";
+    fn test_nested() {
+        let mut bits = reader(NESTED);
+        let packet = decode_packet(&mut bits).unwrap();
 
-        assert_eq!(expected, subpackets_body(OP_BITS));
+        assert_eq!(vec![4, 1, 5, 6], packet.versions());
+        assert_eq!(69, bits.position());
     }
 
     #[test]
-    fn test_operator_count_body() {
-        let expected = "01010000001100100000100011000001100000";
-
-        assert_eq!(expected, subpackets_body(OP_COUNT));
+    fn test_read_across_bytes() {
+        let mut bits = BitReader::new(&[0b1010_1100, 0b0011_0101][..]);
+
+        assert_eq!(0b101, bits.read(3).unwrap());
+        assert_eq!(0b0_1100_0011, bits.read(9).unwrap());
+        assert_eq!(0b0101, bits.read(4).unwrap());
+        assert_eq!(16, bits.position());
+        assert!(bits.read(1).is_err());
     }
 
     #[test]
-    fn test_operator_count_body_2() {
-        let bin = NESTED;
-        let expected = "001010100000000001101010000000000000101111010001111000";
-
-        assert_eq!(expected, subpackets_body(bin));
+    fn test_error_at_field_start() {
+        // a literal whose last group is cut off by the limit
+        let bytes = hex_to_bytes("D2FE2");
+        let mut bits = BitReader::with_limit(&bytes[..], 20);
+        let error = decode_packet(&mut bits).err().unwrap();
+
+        assert_eq!(16, error.bit());
+        assert_eq!("packet ends early at bit 16", error.to_string());
     }
 
     #[test]
-    fn test_lead_packet_1() {
-        let bin = NESTED;
-        let expected = "100010100000000001001010100000000001101010000000000000101111010001111";
-        assert_eq!(expected, lead_packet(bin));
+    fn test_overrun() {
+        // an operator claiming 10 bits of subpackets, holding an 11 bit literal
+        let bin = format!("{}{}{}", "0000000", "000000000001010", "11010001010");
+        let mut bits = reader(&bin);
+        let error = decode_packet(&mut bits).err().unwrap();
+
+        assert_eq!(DecodeError::new(32, "subpackets overrun their length"), error);
     }
 
     #[test]
-    fn test_find_subpackets_bits1() {
-        let expected = vec![
-            "11010001010",
-            "0101001000100100",
-        ];
+    fn test_literal_too_large() {
+        // 33 groups of 4 bits is 132 bits of value
+        let bin = format!("110100{}01111", "11111".repeat(32));
+        let error = decode_packet(&mut reader(&bin)).err().unwrap();
 
-        assert_eq!(expected, find_subpackets(OP_BITS));
+        assert_eq!(DecodeError::new(6, "literal does not fit in 128 bits"), error);
     }
 
     #[test]
-    fn test_find_subpackets_bits2() {
-        let bin = NESTED;
-        let expected = vec![
-            "001010100000000001101010000000000000101111010001111"
-        ];
+    fn test_from_any_reader() {
+        // the literal from above, split across two sources
+        let bytes = bin_to_bytes(LITERAL);
+        let source = Read::chain(&bytes[..1], &bytes[1..]);
+        let literal = decode_packet(&mut BitReader::new(source)).unwrap();
 
-        assert_eq!(expected, find_subpackets(bin));
+        assert_eq!(vec![2021], literal.values());
     }
 
     #[test]
-    fn test_find_subpackets_count1() {
-        let expected = vec![
-            "01010000001",
-            "10010000010",
-            "00110000011",
-        ];
-
-        assert_eq!(expected, find_subpackets(OP_COUNT));
+    fn test_pack() {
+        assert_eq!(vec![0xD2, 0xFE, 0x20], hex_to_bytes("D2FE2"));
+        assert_eq!(vec![0b1101_0010, 0b1000_0000], bin_to_bytes("110100101"));
     }
 }
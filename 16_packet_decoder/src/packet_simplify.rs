@@ -0,0 +1,183 @@
+use crate::packet::{LengthType, OpKind, PacketNode};
+use crate::packet_eval::operate;
+use crate::packet_parse::LEN_PACKETS;
+
+/// Simplify a packet tree without changing its value: nested sums, products,
+/// minimums and maximums are flattened into one, their literal operands are
+/// folded together, operators of a single operand are replaced by it, and
+/// operators of only literals become a literal.
+///
+/// Versions are all set to 0 and every operator counts its subpackets when it
+/// can, so transmissions of the same expression simplify to equal trees.
+/// Operators that cannot be evaluated, like comparisons of three packets or
+/// sums that overflow, are kept as they are, so the tree still has no value.
+/// They are never flattened into the operator around them, where the
+/// overflow might go away, as it would multiplying by 0.
+pub fn simplify(node: &PacketNode) -> PacketNode {
+    node.fold(|node, children| match node {
+        PacketNode::Literal { value, .. } => PacketNode::Literal { version: 0, value: *value },
+        PacketNode::Operator { kind, .. } => simplify_operator(*kind, children),
+    })
+}
+
+// `children` are already simplified
+fn simplify_operator(kind: OpKind, children: Vec<PacketNode>) -> PacketNode {
+    let children = if kind.is_comparison() {
+        children
+    } else {
+        fold_literals(kind, flatten(kind, children))
+    };
+
+    if let Some(value) = literal_values(&children).and_then(|values| operate(kind.type_id(), values).ok()) {
+        return PacketNode::Literal { version: 0, value };
+    }
+    if children.len() == 1 && !kind.is_comparison() {
+        return children.into_iter().next().unwrap();
+    }
+
+    let length_type = if children.len() < 1 << LEN_PACKETS { LengthType::Count } else { LengthType::Bits };
+    PacketNode::Operator { version: 0, kind, length_type, children }
+}
+
+// sums of sums are sums, and so on for the other associative operators,
+// except for those of only literals that overflow
+fn flatten(kind: OpKind, children: Vec<PacketNode>) -> Vec<PacketNode> {
+    let mut flat = Vec::with_capacity(children.len());
+    for child in children {
+        match child {
+            PacketNode::Operator { kind: inner, children, .. } if inner == kind && !overflows(kind, &children) => flat.extend(children),
+            child => flat.push(child),
+        }
+    }
+    flat
+}
+
+// combine the literal operands into one, placed after the others
+fn fold_literals(kind: OpKind, children: Vec<PacketNode>) -> Vec<PacketNode> {
+    let (literals, mut others): (Vec<_>, Vec<_>) = children.into_iter()
+        .partition(|child| matches!(child, PacketNode::Literal { .. }));
+    if literals.is_empty() {
+        return others;
+    }
+
+    match operate(kind.type_id(), literal_values(&literals).unwrap()) {
+        // adding 0 or multiplying by 1 does nothing, unless it is all there is
+        Ok(0) if kind == OpKind::Sum && !others.is_empty() => {},
+        Ok(1) if kind == OpKind::Product && !others.is_empty() => {},
+        Ok(value) => others.push(PacketNode::Literal { version: 0, value }),
+        // too large to fold
+        Err(_) => others.extend(literals),
+    }
+    others
+}
+
+fn overflows(kind: OpKind, children: &[PacketNode]) -> bool {
+    literal_values(children).is_some_and(|values| operate(kind.type_id(), values).is_err())
+}
+
+// the values of `nodes` if they are all literals
+fn literal_values(nodes: &[PacketNode]) -> Option<Vec<u128>> {
+    nodes.iter()
+        .map(|node| match node {
+            PacketNode::Literal { value, .. } => Some(*value),
+            PacketNode::Operator { .. } => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packet::{encode_hex, parse_expression, parse_hex, Packet};
+    use crate::packet_eval::EvalError;
+
+    // too large to fold, so it stands in for an operand whose value is unknown
+    const X: &str = "(18446744073709551616*18446744073709551616)";
+
+    fn simplified(expression: &str) -> String {
+        let node = parse_expression(&expression.replace('X', X)).unwrap().to_node();
+        Box::<dyn Packet>::from(simplify(&node)).eval_string().replace(X, "X")
+    }
+
+    #[test]
+    fn test_flatten() {
+        let node = parse_expression("((1+2)+(3+4)+5)").unwrap().to_node();
+        let flat = flatten(OpKind::Sum, node.children().to_vec());
+
+        assert_eq!(Some(vec![1, 2, 3, 4, 5]), literal_values(&flat));
+    }
+
+    #[test]
+    fn test_fold_constants() {
+        assert_eq!("7", simplified("(min(7,8)*(5<15))"));
+        assert_eq!("(X+X+3)", simplified("((X+1)+(2+X))"));
+        assert_eq!("max(X,2)", simplified("max(X,max(1,2))"));
+        assert_eq!("(X<3)", simplified("(X<(1+2))"));
+        // adding nothing and multiplying by one
+        assert_eq!("X", simplified("(X+(0*5))"));
+        assert_eq!("(X+X)", simplified("((X+X)*(1=1))"));
+    }
+
+    #[test]
+    fn test_single_operand() {
+        assert_eq!("X", simplified("((X))"));
+        assert_eq!("X", simplified("min(min(X))"));
+    }
+
+    #[test]
+    fn test_unfoldable() {
+        // a comparison of three packets has no value, so it is left alone
+        let one = PacketNode::Literal { version: 0, value: 1 };
+        let bad = PacketNode::Operator {
+            version: 0,
+            kind: OpKind::EqualTo,
+            length_type: LengthType::Count,
+            children: vec![one.clone(), one.clone(), one],
+        };
+
+        assert_eq!(bad, simplify(&bad));
+        assert_eq!(X, simplified(X).replace('X', X));
+
+        // the overflow stays, even though it is multiplied by 0
+        assert_eq!("(X*0)", simplified("(0*X)"));
+        let node = parse_expression(&format!("(0*{})", X)).unwrap().to_node();
+        assert_eq!(Err(EvalError::Overflow(OpKind::Product)), Box::<dyn Packet>::from(simplify(&node)).evaluate());
+    }
+
+    #[test]
+    fn test_print_unfoldable_comparisons() {
+        let print = |node: &PacketNode| Box::<dyn Packet>::from(simplify(node)).eval_string();
+
+        // an equal to with a single subpacket
+        let single = parse_hex("1E004408").unwrap().to_node();
+        assert_eq!("(=1)", print(&single));
+
+        let one = PacketNode::Literal { version: 0, value: 1 };
+        let triple = PacketNode::Operator {
+            version: 0,
+            kind: OpKind::LessThan,
+            length_type: LengthType::Bits,
+            children: vec![one.clone(), parse_expression("(2+3)").unwrap().to_node(), one],
+        };
+        assert_eq!("(1<5<1)", print(&triple));
+    }
+
+    #[test]
+    fn test_smaller_transmission() {
+        let packet = parse_hex("9C0141080250320F1802104A08").unwrap();
+        let hex = encode_hex(&*Box::<dyn Packet>::from(simplify(&packet.to_node()))).unwrap();
+
+        assert_eq!("1020", hex);
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        // an operator holding two operators of two literals each, with
+        // different versions and length types
+        let a = parse_hex("C0015000016115A2E0802F182340").unwrap().to_node();
+        let b = parse_hex("620080001611562C8802118E34").unwrap().to_node();
+
+        assert_ne!(a, b);
+        assert_eq!(simplify(&a), simplify(&b));
+    }
+}
@@ -1,23 +1,79 @@
-use crate::packet_parse::{version, type_id};
+use std::fmt;
 
-struct Header {
-    version: u8,
-    type_id: u8,
+use crate::packet_parse::{LITERAL_TYPE_ID, TYPE_ID, VERSION};
+
+/// The fields every packet starts with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Header {
+    pub version: u8,
+    pub type_id: u8,
+}
+
+/// What an operator packet does with its subpackets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OpKind {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
 }
 
-impl Header {
-    fn from(bin: &str) -> Header {
-        Header {
-            version: version(bin),
-            type_id: type_id(bin),
+impl OpKind {
+    /// The operation with this type ID, or `None` for a literal's.
+    pub fn from_type_id(type_id: u8) -> Option<OpKind> {
+        match type_id {
+            0 => Some(OpKind::Sum),
+            1 => Some(OpKind::Product),
+            2 => Some(OpKind::Minimum),
+            3 => Some(OpKind::Maximum),
+            5 => Some(OpKind::GreaterThan),
+            6 => Some(OpKind::LessThan),
+            7 => Some(OpKind::EqualTo),
+            _ => None,
         }
     }
+
+    pub fn type_id(self) -> u8 {
+        match self {
+            OpKind::Sum => 0,
+            OpKind::Product => 1,
+            OpKind::Minimum => 2,
+            OpKind::Maximum => 3,
+            OpKind::GreaterThan => 5,
+            OpKind::LessThan => 6,
+            OpKind::EqualTo => 7,
+        }
+    }
+
+    /// Whether this compares exactly two subpackets.
+    pub fn is_comparison(self) -> bool {
+        matches!(self, OpKind::GreaterThan | OpKind::LessThan | OpKind::EqualTo)
+    }
+}
+
+impl fmt::Display for OpKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OpKind::Sum => "sum",
+            OpKind::Product => "product",
+            OpKind::Minimum => "minimum",
+            OpKind::Maximum => "maximum",
+            OpKind::GreaterThan => "greater than",
+            OpKind::LessThan => "less than",
+            OpKind::EqualTo => "equal to",
+        };
+        f.write_str(name)
+    }
 }
 
 pub mod literal {
-    use crate::packet::{Packet};
-    use crate::packet_parse::{literal_value};
-    use super::{Header};
+    use crate::packet::{EvalError, Packet, PacketNode};
+    use crate::packet_encode::{BitWriter, EncodeError};
+    use crate::packet_parse::GROUP;
+    use super::{Header, LITERAL_TYPE_ID, TYPE_ID, VERSION};
 
     pub struct Literal {
         header: Header,
@@ -25,12 +81,18 @@ pub mod literal {
     }
     
     impl Literal {
-        pub fn new(binary: &str) -> Literal {
-            let header = Header::from(binary);
-            let value = literal_value(binary);
-            let value = u128::from_str_radix(&value, 2).unwrap();
+        pub fn new(version: u8, value: u128) -> Literal {
+            let header = Header { version, type_id: LITERAL_TYPE_ID };
             Literal { header, value }
         }
+
+        pub fn version(&self) -> u8 {
+            self.header.version
+        }
+
+        pub fn value(&self) -> u128 {
+            self.value
+        }
     }
     
     impl Packet for Literal {
@@ -42,60 +104,92 @@ pub mod literal {
             vec![self.value]
         }
 
-        fn evaluate(&self) -> u128 {
-            self.value
+        fn evaluate(&self) -> Result<u128, EvalError> {
+            Ok(self.value)
+        }
+
+        #[cfg(feature = "bigint")]
+        fn evaluate_big(&self) -> Result<crate::packet::BigUint, EvalError> {
+            Ok(self.value.into())
         }
 
         fn eval_string(&self) -> String {
             format!("{}", self.value)
         }
+
+        fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+            bits.write(self.header.version as u32, VERSION);
+            bits.write(LITERAL_TYPE_ID as u32, TYPE_ID);
+
+            // 4 bits of value per group, and always at least one group
+            let groups = (128 - self.value.leading_zeros()).div_ceil(4).max(1);
+            for i in (0..groups).rev() {
+                let more = if i > 0 { 0b10000 } else { 0 };
+                bits.write(more | (self.value >> (4 * i)) as u32 & 0b1111, GROUP);
+            }
+
+            Ok(())
+        }
+
+        fn to_node(&self) -> PacketNode {
+            PacketNode::from(self)
+        }
     }
 }
 
 pub mod operator {
-    use crate::packet::{parse_bin, Packet};
-    use crate::packet_parse::{find_subpackets};
-    use super::{Header};
+    use crate::packet::{EvalError, Packet, PacketNode};
+    use crate::packet_encode::{BitWriter, EncodeError};
+    use crate::packet_eval::operate;
+    use crate::packet_parse::{LEN_BITS, LEN_ID, LEN_PACKETS};
+    use super::{Header, OpKind, TYPE_ID, VERSION};
+
+    /// How an operator gives the size of its subpackets.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum LengthType {
+        // length type ID 0: the total length of the subpackets, in bits
+        Bits,
+        // length type ID 1: the number of subpackets
+        Count,
+    }
 
     pub struct Operator {
         header: Header,
+        length_type: LengthType,
         subpackets: Vec<Box<dyn Packet>>,
     }
     
     impl Operator {
-        pub fn new(binary: &str) -> Operator {
-            let header = Header::from(binary);
-
-            let subpackets = find_subpackets(binary).into_iter()
-                .map(|p| parse_bin(&p))
-                .collect();
-
-            Operator { header, subpackets }
-        }
-
-        fn operate(&self) -> u128 {
-            let mut evaluated = self.evaluated().into_iter();
-            match self.header.type_id {
-                // sum
-                0 => evaluated.sum(),
-                // product
-                1 => evaluated.reduce(|a, b| a * b).unwrap(),
-                // min
-                2 => evaluated.min().unwrap(),
-                // max
-                3 => evaluated.max().unwrap(),
-                // greater
-                5 => if evaluated.next().unwrap() > evaluated.next().unwrap() { 1 } else { 0 },
-                // lesser
-                6 => if evaluated.next().unwrap() < evaluated.next().unwrap() { 1 } else { 0 },
-                // equal
-                7 => if evaluated.next().unwrap() == evaluated.next().unwrap() { 1 } else { 0 },
-                _ => panic!("invalid type id"),
-            }
+        /// An operator that counts its subpackets, unless there are too many
+        /// to count.
+        pub fn new(version: u8, kind: OpKind, subpackets: Vec<Box<dyn Packet>>) -> Operator {
+            let header = Header { version, type_id: kind.type_id() };
+            let length_type = if subpackets.len() < 1 << LEN_PACKETS {
+                LengthType::Count
+            } else {
+                LengthType::Bits
+            };
+            Operator { header, length_type, subpackets }
+        }
+
+        pub fn with_length_type(self, length_type: LengthType) -> Operator {
+            Operator { length_type, ..self }
+        }
+
+        pub fn version(&self) -> u8 {
+            self.header.version
         }
 
-        fn evaluated(&self) -> Vec<u128> {
-            self.subpackets.iter().map(|sp| sp.evaluate()).collect()
+        pub fn kind(&self) -> OpKind {
+            OpKind::from_type_id(self.header.type_id).unwrap()
+        }
+
+        pub fn length_type(&self) -> LengthType {
+            self.length_type
+        }
+
+        pub fn subpackets(&self) -> &[Box<dyn Packet>] {
+            &self.subpackets
         }
     }
     
@@ -108,34 +202,75 @@ pub mod operator {
             self.subpackets.iter().flat_map(|p| p.values()).collect()
         }
 
-        fn evaluate(&self) -> u128 {
-            self.operate()
+        fn evaluate(&self) -> Result<u128, EvalError> {
+            let operands = self.subpackets.iter().map(|sp| sp.evaluate()).collect::<Result<_, _>>()?;
+            operate(self.header.type_id, operands)
+        }
+
+        #[cfg(feature = "bigint")]
+        fn evaluate_big(&self) -> Result<crate::packet::BigUint, EvalError> {
+            let operands = self.subpackets.iter().map(|sp| sp.evaluate_big()).collect::<Result<_, _>>()?;
+            operate(self.header.type_id, operands)
         }
 
         fn eval_string(&self) -> String {
-            match self.header.type_id {
-                r @ 0..=3 => {
-                    let vals: Vec<String> = self.subpackets.iter().map(|sp| sp.eval_string()).collect();
-                    match r {
-                        0 => format!("({})", vals.join("+")),
-                        1 => format!("({})", vals.join("*")),
-                        2 => format!("min({})", vals.join(",")),
-                        3 => format!("max({})", vals.join(",")),
-                        _ => panic!("wish Rust were smarter"),
+            let vals: Vec<String> = self.subpackets.iter().map(|sp| sp.eval_string()).collect();
+            let op = match self.kind() {
+                OpKind::Minimum => return format!("min({})", vals.join(",")),
+                OpKind::Maximum => return format!("max({})", vals.join(",")),
+                OpKind::Sum => "+",
+                OpKind::Product => "*",
+                OpKind::GreaterThan => ">",
+                OpKind::LessThan => "<",
+                OpKind::EqualTo => "=",
+            };
+
+            // a comparison without two packets still shows what it is
+            if self.kind().is_comparison() && vals.len() < 2 {
+                format!("({}{})", op, vals.join(""))
+            } else {
+                format!("({})", vals.join(op))
+            }
+        }
+
+        fn encode(&self, bits: &mut BitWriter) -> Result<(), EncodeError> {
+            if self.subpackets.is_empty() {
+                return Err(EncodeError::NoSubpackets(self.kind()));
+            }
+            bits.write(self.header.version as u32, VERSION);
+            bits.write(self.header.type_id as u32, TYPE_ID);
+
+            match self.length_type {
+                LengthType::Bits => {
+                    let mut body = BitWriter::new();
+                    for subpacket in self.subpackets.iter() {
+                        subpacket.encode(&mut body)?;
                     }
+                    if body.len() >= 1 << LEN_BITS {
+                        return Err(EncodeError::SubpacketsTooLong(body.len()));
+                    }
+                    bits.write(0, LEN_ID);
+                    bits.write(body.len() as u32, LEN_BITS);
+                    bits.append(&body);
                 },
-                b @ 5..=7 =>{
-                    let vals: Vec<String> = self.subpackets.iter().map(|sp| sp.eval_string()).collect();
-                    let op = match b {
-                        5 => ">",
-                        6 => "<",
-                        7 => "=",
-                        _ => panic!("wish Rust were smarter"),
-                    };
-                    format!("({}{}{})", vals[0], op, vals[1])
+                LengthType::Count => {
+                    let count = self.subpackets.len();
+                    if count >= 1 << LEN_PACKETS {
+                        return Err(EncodeError::TooManySubpackets(count));
+                    }
+                    bits.write(1, LEN_ID);
+                    bits.write(count as u32, LEN_PACKETS);
+                    for subpacket in self.subpackets.iter() {
+                        subpacket.encode(bits)?;
+                    }
                 },
-                _ => panic!("invalid type id"),
             }
+
+            Ok(())
+        }
+
+        fn to_node(&self) -> PacketNode {
+            PacketNode::from(self)
         }
     }
 }
\ No newline at end of file
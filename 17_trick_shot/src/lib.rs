@@ -1,9 +1,49 @@
 use std::ops::RangeInclusive;
+use regex::Regex;
+use common::{parse_at, Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(17, "Trick Shot", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Target;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (x, y) = parse_bounds(input)?;
+        if *x.start() <= 0 || *y.end() >= 0 {
+            return Err(ParseError::at(input, input.trim_end(), "the target must be to the right of and below the launcher"));
+        }
+        Ok(Target::new(x, y))
+    }
+
+    fn part_one(target: &Self::Input) -> Answer {
+        max_y(target.clone()).into()
+    }
+
+    fn part_two(target: &Self::Input) -> Answer {
+        let starts = all_starts(target.clone());
+
+        starts.len().into()
+    }
+}
+
+fn parse_bounds(target: &str) -> Result<(RangeInclusive<i32>, RangeInclusive<i32>), ParseError> {
+    let regex = Regex::new(r"-?[0-9]+\.\.-?[0-9]+").unwrap();
+    let bounds = regex.find_iter(target).map(|m| {
+        let (start, end) = m.as_str().split_once("..").unwrap();
+        Ok(parse_at(target, start)?..=parse_at(target, end)?)
+    }).collect::<Result<Vec<_>, ParseError>>()?;
+
+    match &bounds[..] {
+        [x, y] => Ok((x.clone(), y.clone())),
+        _ => Err(ParseError::new(1, 1, "expected a target like `target area: x=20..30, y=-10..-5`")),
+    }
+}
 
 pub fn max_y(target: Target) -> i32 {
     let max_dy = target.bottom().abs() - 1;
-    let max_y = gauss_sum(max_dy);
-    max_y
+    gauss_sum(max_dy)
 }
 
 /*
@@ -128,10 +168,7 @@ pub struct Target {
 impl Target {
     pub fn new(x: RangeInclusive<i32>, y: RangeInclusive<i32>) -> Target {
         assert!(*x.start() > 0 && *y.end() < 0, "invalid target location");
-        Target {
-            x: x,
-            y: y,
-        }
+        Target { x, y }
     }
 
     pub fn test(&self, x: i32, y: i32) -> Result<(), OffBy> {
@@ -196,8 +233,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let target = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(45), Solver::part_one(&target));
+        assert_eq!(Answer::Number(112), Solver::part_two(&target));
     }
 }
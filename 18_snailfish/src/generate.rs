@@ -0,0 +1,40 @@
+//! Proptest strategies for random snailfish numbers, for property tests in
+//! this crate and, with the `proptest` feature, anywhere else.
+
+use proptest::prelude::*;
+use proptest::strategy::Union;
+
+use crate::{SnailNum, SnailTree};
+
+/// Random trees whose deepest regular is exactly `depth` pairs deep, with
+/// regulars up to `max`.
+pub fn tree_at(depth: usize, max: u128) -> BoxedStrategy<SnailTree> {
+    if depth == 0 {
+        return (0..=max).prop_map(SnailTree::Regular).boxed();
+    }
+
+    // one side goes the full depth, the other as deep as it likes
+    let deep = tree_at(depth - 1, max);
+    let shallow = tree_up_to(depth - 1, max);
+    prop_oneof![(deep.clone(), shallow.clone()), (shallow, deep)]
+        .prop_map(|(left, right)| SnailTree::pair(left, right))
+        .boxed()
+}
+
+/// Random trees at most `depth` pairs deep, with regulars up to `max`.
+pub fn tree_up_to(depth: usize, max: u128) -> BoxedStrategy<SnailTree> {
+    Union::new((0..=depth).map(|depth| tree_at(depth, max))).boxed()
+}
+
+/// Random snailfish numbers exactly `depth` pairs deep, with regulars up to
+/// `max`.
+pub fn snail_at(depth: usize, max: u128) -> impl Strategy<Value = SnailNum> {
+    assert!(depth > 0, "snailfish numbers are pairs");
+    tree_at(depth, max).prop_map(|tree| SnailNum::from(&tree))
+}
+
+/// Random reduced snailfish numbers: at most 4 deep, with single digit
+/// regulars.
+pub fn reduced() -> impl Strategy<Value = SnailNum> {
+    (1..=4_usize).prop_flat_map(|depth| snail_at(depth, 9))
+}
@@ -1,6 +1,62 @@
 mod tokenize;
+mod tree;
+#[cfg(any(test, feature = "proptest"))]
+pub mod generate;
+#[cfg(test)]
+mod properties;
+
+use std::fmt;
+use std::iter::Sum;
+use std::str::FromStr;
+use std::thread;
 
 use tokenize::{Token, Tokenizer};
+pub use tree::SnailTree;
+use common::{Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(18, "Snailfish", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<SnailNum>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let snails: Vec<_> = input.lines()
+            .map(|line| SnailNum::parse(line).map_err(|e| e.within(input, line)))
+            .collect::<Result<_, _>>()?;
+        if snails.is_empty() {
+            return Err(ParseError::new(1, 1, "there are no snailfish numbers"));
+        }
+        Ok(snails)
+    }
+
+    fn part_one(snails: &Self::Input) -> Answer {
+        // `parse` makes sure there is at least one
+        SnailNum::try_sum(snails.iter().cloned()).map_or(0, |sum| sum.magnitude()).into()
+    }
+
+    fn part_two(snails: &Self::Input) -> Answer {
+        max_pair_magnitude(snails).into()
+    }
+}
+
+/// One step of reducing a snailfish number. `index` counts regulars from the
+/// left, before the step.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ReduceStep {
+    Explode { index: usize, pair: (u128, u128) },
+    Split { index: usize, value: u128 },
+}
+
+impl fmt::Display for ReduceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReduceStep::Explode { pair: (left, right), .. } => write!(f, "explode [{},{}]", left, right),
+            ReduceStep::Split { value, .. } => write!(f, "split {}", value),
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SnailNum {
@@ -13,9 +69,13 @@ pub struct SnailNum {
 }
 
 impl SnailNum {
-    pub fn parse(string: &str) -> SnailNum {
-        let tokens = Tokenizer::new(string);
+    /// Parse a snailfish number, which must be a pair. Same as `str::parse`.
+    pub fn parse(string: &str) -> Result<SnailNum, ParseError> {
+        string.parse()
+    }
 
+    // build the flat representation from the tokens of a well-formed number
+    fn from_tokens(tokens: impl Iterator<Item = Token>) -> SnailNum {
         let mut regulars = Vec::new();
         let mut brackets = Vec::new();
 
@@ -35,17 +95,13 @@ impl SnailNum {
                 Token::BracketRight => {
                     delta -= 1;
                 }
+                Token::Invalid(c) => panic!("invalid char {}", c),
                 Token::Int(value) => {
                     let value = value.parse::<u128>().unwrap();
                     regulars.push(value);
-                    if let Some(token) = last_token {
-                        match token {
-                            Token::BracketLeft | Token::BracketRight => {
-                                brackets.push(delta);
-                                delta = 0;
-                            },
-                            _ => (),
-                        }
+                    if let Some(Token::BracketLeft | Token::BracketRight) = last_token {
+                        brackets.push(delta);
+                        delta = 0;
                     }
                 }
             }
@@ -65,41 +121,55 @@ impl SnailNum {
     }
 
     pub fn reduce(&mut self) {
-        'outer: loop {
-            // try explode
-            let mut depth = self.brackets[0];
-            for i in 0..self.regulars.len() {
-                if
-                    depth >= 5 && 
-                    i + 1 < self.regulars.len() &&
-                    self.brackets[i].signum() == 1 && self.brackets[i + 1].signum() == -1
-                {
-                    self.explode(i);
-                    continue 'outer;
-                } else {
-                    if self.brackets[i].signum() == -1 {
-                        depth += self.brackets[i];
-                    }
-                    if i+1 < self.regulars.len() {
-                        if self.brackets[i+1].signum() == 1 {
-                            depth += self.brackets[i+1];
-                        }
-                    }
-                }
-            }
+        while self.reduce_step().is_some() {}
+
+        self.check_rep();
+    }
 
-            // try split
-            for i in 0..self.regulars.len() {
-                if self.regulars[i] > 9 {
-                    self.split(i);
-                    continue 'outer;
+    /// Reduce one step at a time, yielding each explode or split along with
+    /// the number it leaves behind.
+    pub fn reduce_traced(mut self) -> impl Iterator<Item = (ReduceStep, SnailNum)> {
+        std::iter::from_fn(move || {
+            let step = self.reduce_step()?;
+            Some((step, self.clone()))
+        })
+    }
+
+    // explode the leftmost pair that can be, or failing that split the
+    // leftmost regular that can be
+    fn reduce_step(&mut self) -> Option<ReduceStep> {
+        // try explode
+        let mut depth = self.brackets[0];
+        for i in 0..self.regulars.len() {
+            if
+                depth >= 5 && 
+                i + 1 < self.regulars.len() &&
+                self.brackets[i].signum() == 1 && self.brackets[i + 1].signum() == -1
+            {
+                let pair = (self.regulars[i], self.regulars[i + 1]);
+                self.explode(i);
+                return Some(ReduceStep::Explode { index: i, pair });
+            } else {
+                if self.brackets[i].signum() == -1 {
+                    depth += self.brackets[i];
                 }
+                if i+1 < self.regulars.len()
+                    && self.brackets[i+1].signum() == 1 {
+                        depth += self.brackets[i+1];
+                    }
             }
+        }
 
-            break 'outer;
+        // try split
+        for i in 0..self.regulars.len() {
+            if self.regulars[i] > 9 {
+                let value = self.regulars[i];
+                self.split(i);
+                return Some(ReduceStep::Split { index: i, value });
+            }
         }
 
-        self.check_rep();
+        None
     }
 
     fn explode(&mut self, i: usize) {
@@ -140,7 +210,7 @@ impl SnailNum {
         // split
         let value = self.regulars[i];
         let left = value / 2;
-        let right = (value / 2) + if value % 2 != 0 { 1 } else { 0 };
+        let right = (value / 2) + if !value.is_multiple_of(2) { 1 } else { 0 };
 
         self.regulars[i] = left;
         self.regulars.insert(i + 1, right);
@@ -156,6 +226,12 @@ impl SnailNum {
         self.check_rep();
     }
 
+    /// The sum of `snails`, or `None` if there are none, since snailfish
+    /// numbers have no zero.
+    pub fn try_sum(snails: impl IntoIterator<Item = SnailNum>) -> Option<SnailNum> {
+        snails.into_iter().reduce(|sum, snail| sum + snail)
+    }
+
     pub fn magnitude(&self) -> u128 {
         self.check_rep();
 
@@ -188,14 +264,61 @@ impl SnailNum {
     }
 }
 
+impl FromStr for SnailNum {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut tokens = Tokenizer::new(string);
+        let tree = parse_element(&mut tokens)?;
+        if let SnailTree::Regular(_) = tree {
+            return Err(ParseError::new(1, 1, "expected a pair"));
+        }
+        if tokens.next().is_some() {
+            return Err(token_error(&tokens, "expected the end of the number"));
+        }
+
+        Ok(SnailNum::from(&tree))
+    }
+}
+
+// a pair or a regular number, starting at the next token
+fn parse_element(tokens: &mut Tokenizer) -> Result<SnailTree, ParseError> {
+    match tokens.next() {
+        Some(Token::Int(digits)) => digits.parse()
+            .map(SnailTree::Regular)
+            .map_err(|_| token_error(tokens, "regular number is too large")),
+        Some(Token::BracketLeft) => {
+            let left = parse_element(tokens)?;
+            expect(tokens, Token::Comma, "expected `,`")?;
+            let right = parse_element(tokens)?;
+            expect(tokens, Token::BracketRight, "expected `]`")?;
+            Ok(SnailTree::pair(left, right))
+        },
+        _ => Err(token_error(tokens, "expected `[` or a regular number")),
+    }
+}
+
+fn expect(tokens: &mut Tokenizer, token: Token, explanation: &str) -> Result<(), ParseError> {
+    if tokens.next() == Some(token) {
+        Ok(())
+    } else {
+        Err(token_error(tokens, explanation))
+    }
+}
+
+// an error at the token just read
+fn token_error(tokens: &Tokenizer, explanation: &str) -> ParseError {
+    ParseError::new(1, tokens.position() + 1, explanation)
+}
+
 use std::ops::Add;
 
 impl Add for SnailNum {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        let regulars: Vec<u128> = self.regulars.into_iter().chain(other.regulars.into_iter()).collect();
-        let mut brackets: Vec<i32> = self.brackets.into_iter().chain(other.brackets.into_iter()).collect();
+        let regulars: Vec<u128> = self.regulars.into_iter().chain(other.regulars).collect();
+        let mut brackets: Vec<i32> = self.brackets.into_iter().chain(other.brackets).collect();
         brackets[0] += 1;
         let last = brackets.len() - 1;
         brackets[last] -= 1;
@@ -207,7 +330,52 @@ impl Add for SnailNum {
     }
 }
 
-use std::fmt;
+impl<'a> Add<&'a SnailNum> for SnailNum {
+    type Output = Self;
+
+    fn add(self, other: &'a SnailNum) -> Self {
+        self + other.clone()
+    }
+}
+
+/// Panics on an empty iterator, since snailfish numbers have no zero; use
+/// `SnailNum::try_sum` where there may be none.
+impl Sum for SnailNum {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        SnailNum::try_sum(iter).expect("cannot sum no snailfish numbers")
+    }
+}
+
+impl<'a> Sum<&'a SnailNum> for SnailNum {
+    fn sum<I: Iterator<Item = &'a SnailNum>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// The largest magnitude of the sum of two different numbers, in either
+/// order, with the pairs shared out across threads.
+pub fn max_pair_magnitude(snails: &[SnailNum]) -> u128 {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get()).min(snails.len().max(1));
+
+    thread::scope(|scope| {
+        // thread t takes the pairs starting with every t-th number
+        let handles: Vec<_> = (0..threads)
+            .map(|t| scope.spawn(move || {
+                let mut max_mag = 0;
+                for i in (t..snails.len()).step_by(threads) {
+                    for j in 0..snails.len() {
+                        if i != j {
+                            max_mag = max_mag.max((snails[i].clone() + &snails[j]).magnitude());
+                        }
+                    }
+                }
+                max_mag
+            }))
+            .collect();
+
+        handles.into_iter().map(|handle| handle.join().unwrap()).max().unwrap_or(0)
+    })
+}
 
 impl fmt::Display for SnailNum {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -236,7 +404,7 @@ mod tests {
     use super::*;
 
     fn test_parse(to_parse: &str) {
-        let snail = SnailNum::parse(to_parse);
+        let snail = SnailNum::parse(to_parse).unwrap();
         assert_eq!(to_parse, &format!("{}", snail));
     }
 
@@ -277,15 +445,15 @@ mod tests {
 
     #[test]
     fn test_reduce_1() {
-        let mut snail = SnailNum::parse("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+        let mut snail = SnailNum::parse("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]").unwrap();
         snail.reduce();
-        let expected = SnailNum::parse("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+        let expected = SnailNum::parse("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap();
         assert_eq!(expected, snail);
     }
 
     #[test]
     fn test_reduce_2() {
-        let mut snail = SnailNum::parse("[0,[0,[0,[20,0]]]]");
+        let mut snail = SnailNum::parse("[0,[0,[0,[20,0]]]]").unwrap();
         snail.reduce();
 
         /*
@@ -296,68 +464,68 @@ mod tests {
         [0,[0,[[5,5],[5,0]]]]
         */
 
-        let expected = SnailNum::parse("[0,[0,[[5,5],[5,0]]]]");
+        let expected = SnailNum::parse("[0,[0,[[5,5],[5,0]]]]").unwrap();
 
         assert_eq!(expected, snail);
     }
 
     #[test]
     fn test_add_1() {
-        let left = SnailNum::parse("[[[[4,3],4],4],[7,[[8,4],9]]]");
-        let right = SnailNum::parse("[1,1]");
+        let left = SnailNum::parse("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap();
+        let right = SnailNum::parse("[1,1]").unwrap();
 
         let sum = left + right;
-        let expected = SnailNum::parse("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+        let expected = SnailNum::parse("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap();
         assert_eq!(expected, sum);
     }
 
     #[test]
     fn test_add_2() {
-        let left = SnailNum::parse("[[1,2],[3,4]]");
-        let right = SnailNum::parse("[[5,6],[7,8]]");
+        let left = SnailNum::parse("[[1,2],[3,4]]").unwrap();
+        let right = SnailNum::parse("[[5,6],[7,8]]").unwrap();
 
         let sum = left + right;
-        let expected = SnailNum::parse("[[[1,2],[3,4]],[[5,6],[7,8]]]");
+        let expected = SnailNum::parse("[[[1,2],[3,4]],[[5,6],[7,8]]]").unwrap();
         assert_eq!(expected, sum);
     }
 
     #[test]
     fn test_add_3() {
-        let left = SnailNum::parse("[6,7]");
-        let right = SnailNum::parse("[[[[1,2],3],4],5]");
+        let left = SnailNum::parse("[6,7]").unwrap();
+        let right = SnailNum::parse("[[[[1,2],3],4],5]").unwrap();
 
         let sum = left + right;
-        let expected = SnailNum::parse("[[6,8],[[[0,5],4],5]]");
+        let expected = SnailNum::parse("[[6,8],[[[0,5],4],5]]").unwrap();
         assert_eq!(expected, sum);
     }
 
     #[test]
     fn test_add_4() {
-        let left = SnailNum::parse("[6,9]");
-        let right = SnailNum::parse("[[[[1,2],3],4],5]");
+        let left = SnailNum::parse("[6,9]").unwrap();
+        let right = SnailNum::parse("[[[[1,2],3],4],5]").unwrap();
 
         let sum = left + right;
-        let expected = SnailNum::parse("[[6,[5,5]],[[[0,5],4],5]]");
+        let expected = SnailNum::parse("[[6,[5,5]],[[[0,5],4],5]]").unwrap();
         assert_eq!(expected, sum);
     }
 
     #[test]
     fn test_add_5() {
-        let left = SnailNum::parse("[1,[2,[3,[4,5]]]]");
-        let right = SnailNum::parse("[[[[5,4],3],2],1]");
+        let left = SnailNum::parse("[1,[2,[3,[4,5]]]]").unwrap();
+        let right = SnailNum::parse("[[[[5,4],3],2],1]").unwrap();
 
         let sum = left + right;
-        let expected = SnailNum::parse("[[1,[8,[0,6]]],[[[5,7],2],1]]");
+        let expected = SnailNum::parse("[[1,[8,[0,6]]],[[[5,7],2],1]]").unwrap();
         assert_eq!(expected, sum);
     }
 
     #[test]
     fn test_add_6() {
-        let left = SnailNum::parse("[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]");
-        let right = SnailNum::parse("[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]");
+        let left = SnailNum::parse("[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]").unwrap();
+        let right = SnailNum::parse("[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]").unwrap();
 
         let sum = left + right;
-        let expected = SnailNum::parse("[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]");
+        let expected = SnailNum::parse("[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]").unwrap();
         assert_eq!(expected, sum);
     }
 
@@ -368,9 +536,9 @@ mod tests {
             "[2,2]",
             "[3,3]",
             "[4,4]",
-        ].into_iter().map(|num| SnailNum::parse(num)).reduce(|a, b| a + b).unwrap();
+        ].into_iter().map(|s| SnailNum::parse(s).unwrap()).reduce(|a, b| a + b).unwrap();
 
-        let expected = SnailNum::parse("[[[[1,1],[2,2]],[3,3]],[4,4]]");
+        let expected = SnailNum::parse("[[[[1,1],[2,2]],[3,3]],[4,4]]").unwrap();
 
         assert_eq!(expected, sum);
     }
@@ -383,9 +551,9 @@ mod tests {
             "[3,3]",
             "[4,4]",
             "[5,5]",
-        ].into_iter().map(|num| SnailNum::parse(num)).reduce(|a, b| a + b).unwrap();
+        ].into_iter().map(|s| SnailNum::parse(s).unwrap()).reduce(|a, b| a + b).unwrap();
 
-        let expected = SnailNum::parse("[[[[3,0],[5,3]],[4,4]],[5,5]]");
+        let expected = SnailNum::parse("[[[[3,0],[5,3]],[4,4]],[5,5]]").unwrap();
 
         assert_eq!(expected, sum);
     }
@@ -399,9 +567,9 @@ mod tests {
             "[4,4]",
             "[5,5]",
             "[6,6]",
-        ].into_iter().map(|num| SnailNum::parse(num)).reduce(|a, b| a + b).unwrap();
+        ].into_iter().map(|s| SnailNum::parse(s).unwrap()).reduce(|a, b| a + b).unwrap();
 
-        let expected = SnailNum::parse("[[[[5,0],[7,4]],[5,5]],[6,6]]");
+        let expected = SnailNum::parse("[[[[5,0],[7,4]],[5,5]],[6,6]]").unwrap();
 
         assert_eq!(expected, sum);
     }
@@ -419,7 +587,7 @@ mod tests {
             "[1,[[[9,3],9],[[9,0],[0,7]]]]",
             "[[[5,[7,4]],7],1]",
             "[[[[4,2],2],6],[8,7]]",
-        ].into_iter().map(|num| SnailNum::parse(num));
+        ].into_iter().map(|s| SnailNum::parse(s).unwrap());
 
         let intermediates: Vec<_> = vec![
             "[[[[4,0],[5,4]],[[7,7],[6,0]]],[[8,[7,7]],[[7,9],[5,0]]]]",
@@ -431,7 +599,7 @@ mod tests {
             "[[[[7,8],[6,7]],[[6,8],[0,8]]],[[[7,7],[5,0]],[[5,5],[5,6]]]]",
             "[[[[7,7],[7,7]],[[8,7],[8,7]]],[[[7,0],[7,7]],9]]",
             "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
-        ].into_iter().map(|num| SnailNum::parse(num)).collect();
+        ].into_iter().map(|s| SnailNum::parse(s).unwrap()).collect();
 
         let mut sum = snails.next().unwrap();
 
@@ -448,57 +616,145 @@ mod tests {
 
     #[test]
     fn test_magnitude_simple() {
-        let mag = SnailNum::parse("[9,1]").magnitude();
+        let mag = SnailNum::parse("[9,1]").unwrap().magnitude();
         let expected = 29;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_nested() {
-        let mag = SnailNum::parse("[[9,1],[1,9]]").magnitude();
+        let mag = SnailNum::parse("[[9,1],[1,9]]").unwrap().magnitude();
         let expected = 129;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_1() {
-        let mag = SnailNum::parse("[[1,2],[[3,4],5]]").magnitude();
+        let mag = SnailNum::parse("[[1,2],[[3,4],5]]").unwrap().magnitude();
         let expected = 143;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_2() {
-        let mag = SnailNum::parse("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").magnitude();
+        let mag = SnailNum::parse("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").unwrap().magnitude();
         let expected = 1384;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_3() {
-        let mag = SnailNum::parse("[[[[1,1],[2,2]],[3,3]],[4,4]]").magnitude();
+        let mag = SnailNum::parse("[[[[1,1],[2,2]],[3,3]],[4,4]]").unwrap().magnitude();
         let expected = 445;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_4() {
-        let mag = SnailNum::parse("[[[[3,0],[5,3]],[4,4]],[5,5]]").magnitude();
+        let mag = SnailNum::parse("[[[[3,0],[5,3]],[4,4]],[5,5]]").unwrap().magnitude();
         let expected = 791;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_5() {
-        let mag = SnailNum::parse("[[[[5,0],[7,4]],[5,5]],[6,6]]").magnitude();
+        let mag = SnailNum::parse("[[[[5,0],[7,4]],[5,5]],[6,6]]").unwrap().magnitude();
         let expected = 1137;
         assert_eq!(expected, mag);
     }
 
     #[test]
     fn test_magnitude_6() {
-        let mag = SnailNum::parse("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude();
+        let mag = SnailNum::parse("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").unwrap().magnitude();
         let expected = 3488;
         assert_eq!(expected, mag);
     }
-}
+
+    #[test]
+    fn test_parse_invalid() {
+        let error = SnailNum::parse("[[1,2],[3 4]]").unwrap_err();
+        assert_eq!((1, 11), (error.line(), error.column()));
+
+        let error = SnailNum::parse("[1,2]]").unwrap_err();
+        assert_eq!((1, 6), (error.line(), error.column()));
+    }
+
+    #[test]
+    fn test_reduce_traced_explode() {
+        let snail = SnailNum::parse("[[[[[9,8],1],2],3],4]").unwrap();
+        let steps: Vec<_> = snail.reduce_traced().collect();
+        let expected = SnailNum::parse("[[[[0,9],2],3],4]").unwrap();
+
+        assert_eq!(vec![(ReduceStep::Explode { index: 0, pair: (9, 8) }, expected)], steps);
+    }
+
+    #[test]
+    fn test_reduce_traced() {
+        // the unreduced sum of [[[[4,3],4],4],[7,[[8,4],9]]] and [1,1]
+        let snail = SnailNum::from(&SnailTree::pair(
+            SnailTree::from(&SnailNum::parse("[[[[4,3],4],4],[7,[[8,4],9]]]").unwrap()),
+            SnailTree::from(&SnailNum::parse("[1,1]").unwrap()),
+        ));
+        let steps: Vec<_> = snail.reduce_traced()
+            .map(|(step, snail)| format!("{}: {}", step, snail))
+            .collect();
+        let expected = vec![
+            "explode [4,3]: [[[[0,7],4],[7,[[8,4],9]]],[1,1]]",
+            "explode [8,4]: [[[[0,7],4],[15,[0,13]]],[1,1]]",
+            "split 15: [[[[0,7],4],[[7,8],[0,13]]],[1,1]]",
+            "split 13: [[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]",
+            "explode [6,7]: [[[[0,7],4],[[7,8],[6,0]]],[8,1]]",
+        ];
+
+        assert_eq!(expected, steps);
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        let error = |s: &str| {
+            let error = s.parse::<SnailNum>().unwrap_err();
+            (error.column(), error.explanation().to_string())
+        };
+
+        assert_eq!((4, String::from("expected `[` or a regular number")), error("[1,x]"));
+        assert_eq!((5, String::from("expected `]`")), error("[1,2"));
+        assert_eq!((1, String::from("expected a pair")), error("7"));
+        assert_eq!((2, String::from("regular number is too large")), error(&format!("[{}0,1]", u128::MAX)));
+    }
+
+    #[test]
+    fn test_sum() {
+        let snails: Vec<SnailNum> = ["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"].iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        assert_eq!(SnailNum::parse("[[[[5,0],[7,4]],[5,5]],[6,6]]").unwrap(), snails.iter().sum());
+        assert_eq!(None, SnailNum::try_sum(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let error = Solver::parse("").unwrap_err();
+
+        assert_eq!("line 1, column 1: there are no snailfish numbers", error.to_string());
+    }
+
+    #[test]
+    fn test_max_pair_magnitude() {
+        let snails: Vec<SnailNum> = [
+            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
+            "[[[5,[2,8]],4],[5,[[9,9],0]]]",
+            "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]",
+            "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]",
+            "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]",
+            "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]",
+            "[[[[5,4],[7,7]],8],[[8,3],8]]",
+            "[[9,3],[[9,9],[6,[4,9]]]]",
+            "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]",
+            "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]",
+        ].iter().map(|s| s.parse().unwrap()).collect();
+
+        assert_eq!(3993, max_pair_magnitude(&snails));
+        assert_eq!(0, max_pair_magnitude(&snails[..1]));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+
+use crate::generate::{reduced, snail_at, tree_at};
+use crate::{SnailNum, SnailTree};
+
+fn is_reduced(snail: &SnailNum) -> bool {
+    let tree = SnailTree::from(snail);
+    tree.depth() <= 4 && snail.regulars.iter().all(|&regular| regular < 10)
+}
+
+#[test]
+fn generator_hits_depth() {
+    let mut runner = proptest::test_runner::TestRunner::default();
+    for depth in 1..=6 {
+        let snail = snail_at(depth, 20).new_tree(&mut runner).unwrap().current();
+        assert_eq!(depth, SnailTree::from(&snail).depth());
+    }
+}
+
+proptest! {
+    #[test]
+    fn display_round_trips(snail in (1..=6_usize).prop_flat_map(|depth| snail_at(depth, 30))) {
+        let shown = snail.to_string();
+        let parsed: SnailNum = shown.parse().unwrap();
+
+        prop_assert_eq!(&snail, &parsed);
+        prop_assert_eq!(shown, parsed.to_string());
+    }
+
+    #[test]
+    fn tree_round_trips(tree in (1..=6_usize).prop_flat_map(|depth| tree_at(depth, 30))) {
+        let snail = SnailNum::from(&tree);
+
+        prop_assert_eq!(&tree, &SnailTree::from(&snail));
+        prop_assert_eq!(tree.magnitude(), snail.magnitude());
+    }
+
+    #[test]
+    fn magnitude_survives_reparse(snail in (1..=6_usize).prop_flat_map(|depth| snail_at(depth, 30))) {
+        let parsed: SnailNum = snail.to_string().parse().unwrap();
+
+        prop_assert_eq!(snail.magnitude(), parsed.magnitude());
+    }
+
+    #[test]
+    fn sums_are_reduced(a in reduced(), b in reduced()) {
+        prop_assert!(is_reduced(&(a + b)));
+    }
+
+    #[test]
+    fn reduction_never_leaves_an_unreduced_number(a in reduced(), b in reduced(), c in reduced()) {
+        let sum: SnailNum = [a, b, c].into_iter().sum();
+
+        prop_assert!(is_reduced(&sum));
+        // reducing again does nothing
+        prop_assert_eq!(0, sum.reduce_traced().count());
+    }
+
+    #[test]
+    fn traced_reduction_matches_reduce(a in reduced(), b in reduced()) {
+        // pair the numbers up without reducing
+        let unreduced = SnailNum::from(&SnailTree::pair(SnailTree::from(&a), SnailTree::from(&b)));
+        let traced = unreduced.clone().reduce_traced().last().map_or(unreduced, |(_, snail)| snail);
+
+        prop_assert_eq!(a + b, traced);
+    }
+}
@@ -4,11 +4,15 @@ pub enum Token {
     BracketRight,
     Comma,
     Int(String),
+    // any other char that is not whitespace
+    Invalid(char),
 }
 
 pub struct Tokenizer {
     string: Vec<char>,
     i: usize,
+    // where the last token returned started
+    start: usize,
 }
 
 impl Tokenizer {
@@ -16,8 +20,15 @@ impl Tokenizer {
         Tokenizer {
             string: string.chars().collect(),
             i: 0,
+            start: 0,
         }
     }
+
+    /// The index of the char that the last token returned started at, or the
+    /// number of chars once there are no tokens left.
+    pub fn position(&self) -> usize {
+        self.start
+    }
 }
 
 impl Iterator for Tokenizer {
@@ -25,9 +36,11 @@ impl Iterator for Tokenizer {
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.i >= self.string.len() {
+            self.start = self.string.len();
             None
         } else {
             let token = loop {
+                self.start = self.i;
                 match self.string[self.i] {
                     '[' => break Token::BracketLeft,
                     ']' => break Token::BracketRight,
@@ -35,20 +48,21 @@ impl Iterator for Tokenizer {
                     c if c.is_whitespace() => {
                         self.i += 1;
                         if self.i >= self.string.len() {
+                            self.start = self.string.len();
                             return None
                         } else {
                             continue
                         }
                     },
-                    c if c.is_digit(10) => {
+                    c if c.is_ascii_digit() => {
                         let mut int = String::from(c);
-                        while self.i + 1 < self.string.len() && self.string[self.i + 1].is_digit(10) {
+                        while self.i + 1 < self.string.len() && self.string[self.i + 1].is_ascii_digit() {
                             self.i += 1;
                             int.push(self.string[self.i]);
                         }
                         break Token::Int(int);
                     },
-                    c => panic!("invalid char {}", c),
+                    c => break Token::Invalid(c),
                 }
             };
             self.i += 1;
@@ -94,4 +108,20 @@ mod tests {
 
         assert_eq!(expected, tokens);
     }
+
+    #[test]
+    fn tokenize_positions() {
+        let mut tokens = Tokenizer::new("[ 12,x]");
+        let positions: Vec<(usize, Token)> = std::iter::from_fn(|| tokens.next().map(|token| (tokens.position(), token))).collect();
+        let expected = vec![
+            (0, Token::BracketLeft),
+            (2, Token::Int(String::from("12"))),
+            (4, Token::Comma),
+            (5, Token::Invalid('x')),
+            (6, Token::BracketRight),
+        ];
+
+        assert_eq!(expected, positions);
+        assert_eq!(7, tokens.position());
+    }
 }
\ No newline at end of file
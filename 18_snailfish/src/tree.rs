@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::tokenize::Token;
+use crate::SnailNum;
+
+/// A snailfish number as the nested pairs it is written as, which is easier
+/// to inspect than the flat `SnailNum`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SnailTree {
+    Regular(u128),
+    Pair(Box<SnailTree>, Box<SnailTree>),
+}
+
+impl SnailTree {
+    pub fn pair(left: SnailTree, right: SnailTree) -> SnailTree {
+        SnailTree::Pair(Box::new(left), Box::new(right))
+    }
+
+    /// How many pairs deep the most nested regular is.
+    pub fn depth(&self) -> usize {
+        match self {
+            SnailTree::Regular(_) => 0,
+            SnailTree::Pair(left, right) => 1 + left.depth().max(right.depth()),
+        }
+    }
+
+    pub fn magnitude(&self) -> u128 {
+        match self {
+            SnailTree::Regular(value) => *value,
+            SnailTree::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    fn tokens(&self, tokens: &mut Vec<Token>) {
+        match self {
+            SnailTree::Regular(value) => tokens.push(Token::Int(value.to_string())),
+            SnailTree::Pair(left, right) => {
+                tokens.push(Token::BracketLeft);
+                left.tokens(tokens);
+                tokens.push(Token::Comma);
+                right.tokens(tokens);
+                tokens.push(Token::BracketRight);
+            },
+        }
+    }
+}
+
+impl fmt::Display for SnailTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnailTree::Regular(value) => write!(f, "{}", value),
+            SnailTree::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+impl From<&SnailNum> for SnailTree {
+    fn from(snail: &SnailNum) -> Self {
+        // `None` marks an open bracket whose pair is still being built
+        let mut stack: Vec<Option<SnailTree>> = Vec::new();
+
+        for (&value, &brackets) in snail.regulars.iter().zip(snail.brackets.iter()) {
+            for _ in 0..brackets.max(0) {
+                stack.push(None);
+            }
+            stack.push(Some(SnailTree::Regular(value)));
+            for _ in 0..(-brackets).max(0) {
+                let right = stack.pop().flatten().unwrap();
+                let left = stack.pop().flatten().unwrap();
+                assert!(stack.pop().unwrap().is_none(), "brackets do not match");
+                stack.push(Some(SnailTree::pair(left, right)));
+            }
+        }
+
+        assert_eq!(1, stack.len(), "brackets do not match");
+        stack.pop().flatten().unwrap()
+    }
+}
+
+impl From<&SnailTree> for SnailNum {
+    fn from(tree: &SnailTree) -> Self {
+        let mut tokens = Vec::new();
+        tree.tokens(&mut tokens);
+        SnailNum::from_tokens(tokens.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [&str; 5] = [
+        "[1,2]",
+        "[[1,2],3]",
+        "[9,[8,7]]",
+        "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]",
+        "[[[[[9,8],1],2],3],4]",
+    ];
+
+    #[test]
+    fn test_round_trip() {
+        for example in EXAMPLES {
+            let snail = SnailNum::parse(example).unwrap();
+            let tree = SnailTree::from(&snail);
+
+            assert_eq!(example, tree.to_string());
+            assert_eq!(snail, SnailNum::from(&tree));
+            assert_eq!(snail.magnitude(), tree.magnitude());
+        }
+    }
+
+    #[test]
+    fn test_structure() {
+        let tree = SnailTree::from(&SnailNum::parse("[[1,2],3]").unwrap());
+        let expected = SnailTree::pair(
+            SnailTree::pair(SnailTree::Regular(1), SnailTree::Regular(2)),
+            SnailTree::Regular(3),
+        );
+
+        assert_eq!(expected, tree);
+        assert_eq!(2, tree.depth());
+    }
+
+    #[test]
+    fn test_depth() {
+        let depth = |s| SnailTree::from(&SnailNum::parse(s).unwrap()).depth();
+
+        assert_eq!(5, depth("[[[[[9,8],1],2],3],4]"));
+        assert_eq!(1, depth("[1,2]"));
+    }
+}
@@ -1,4 +1,59 @@
 use std::collections::HashSet;
+use common::{parse_at, Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(19, "Beacon Scanner", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Scanner>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_scanners(input)
+    }
+
+    fn part_one(scanners: &Self::Input) -> Answer {
+        let scanner_set = ScannerSet::assemble(scanners.clone(), 12);
+
+        scanner_set.unqiue_beacons().len().into()
+    }
+
+    fn part_two(scanners: &Self::Input) -> Answer {
+        let scanner_set = ScannerSet::assemble(scanners.clone(), 12);
+
+        scanner_set.max_manhattan().into()
+    }
+}
+
+fn parse_scanners(input: &str) -> Result<Vec<Scanner>, ParseError> {
+    let mut scanners = Vec::new();
+    let mut current_beacons = Vec::new();
+
+    for line in input.lines() {
+        if line.starts_with("---") {
+            current_beacons = Vec::new();
+        } else if !line.is_empty() {
+            let nums = line.split(',')
+                .map(|num| parse_at(input, num))
+                .collect::<Result<Vec<i32>, _>>()?;
+            if nums.len() != 3 {
+                return Err(ParseError::at(input, line, format!("expected 3 coordinates, found {}", nums.len())));
+            }
+            current_beacons.push(Beacon { x: nums[0], y: nums[1], z: nums[2] })
+        } else if !current_beacons.is_empty() {
+            scanners.push(Scanner::from(&current_beacons));
+            current_beacons = Vec::new();
+        }
+    }
+    if !current_beacons.is_empty() {
+        scanners.push(Scanner::from(&current_beacons));
+    }
+    if scanners.is_empty() {
+        return Err(ParseError::new(1, 1, "there are no scanners"));
+    }
+
+    Ok(scanners)
+}
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub struct Coord {
@@ -62,7 +117,7 @@ mod scanner {
     }
     
     impl Scanner {
-        pub fn from(beacons: &Vec<Beacon>) -> Scanner {
+        pub fn from(beacons: &[Beacon]) -> Scanner {
             let mut beacons_matrix = Array::zeros((0, 3));
             beacons.iter().for_each(|b| {
                 beacons_matrix.push_row(ArrayView::from(&[b.x, b.y, b.z])).unwrap();
@@ -155,7 +210,7 @@ impl ScannerSet {
         let initial = remaining.remove(0);
         placements.push((initial, Coord::new(0, 0, 0)));
 
-        while remaining.len() > 0 {
+        while !remaining.is_empty() {
             let mut placement = None;
             'outer: for (i, scanner) in remaining.iter().enumerate() {
                 for perm in scanner.perms() {
@@ -179,7 +234,7 @@ impl ScannerSet {
 
         for (scanner, origin) in self.placements.iter() {
             for beacon in scanner.beacons() {
-                uniques.insert(beacon.translate(&origin));
+                uniques.insert(beacon.translate(origin));
             }
         }
 
@@ -205,12 +260,12 @@ mod tests {
             (1, 1, 1),
             (2, 3, 4),
             (799, 44, -3),
-        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect());
+        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect::<Vec<_>>());
         let s1 = Scanner::from(&[
             (2, 2, 2),
             (3, 4, 5),
             (800, 45, -2)
-        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect());
+        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect::<Vec<_>>());
 
         let expected = Beacon::new(-1, -1, -1);
 
@@ -252,7 +307,7 @@ mod tests {
             (443,580,662),
             (-789,900,-551),
             (459,-707,401),
-        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect());
+        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect::<Vec<_>>());
         let s1 = Scanner::from(&[
             (686,422,578),
             (605,423,415),
@@ -279,7 +334,7 @@ mod tests {
             (807,-499,-711),
             (755,-354,-619),
             (553,889,-390),
-        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect());
+        ].iter().map(|b| Beacon { x: b.0, y: b.1, z: b.2 }).collect::<Vec<_>>());
 
         let expected = Beacon::new(68,-1246,-43);
 
@@ -292,4 +347,13 @@ mod tests {
 
         panic!("did not find overlap");
     }
+
+    #[test]
+    fn no_scanners() {
+        for input in ["", "\n\n", "--- scanner 0 ---\n"] {
+            let error = Solver::parse(input).err().unwrap();
+
+            assert_eq!("line 1, column 1: there are no scanners", error.to_string());
+        }
+    }
 }
@@ -1,150 +1,123 @@
-use std::collections::HashSet;
+use common::{Answer, Day, Grid, ParseError, Point, SparseGrid, Solution};
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-struct Coord {
-    pub r: i32,
-    pub c: i32,
-}
+pub const DAY: Day = Day::new::<Solver>(20, "Trench Map", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = ImageEnhancer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        ImageEnhancer::parse(input)
+    }
+
+    fn part_one(trench_map: &Self::Input) -> Answer {
+        enhanced_lit_count(trench_map, 2)
+    }
 
-impl Coord {
-    pub fn new(r: i32, c: i32) -> Coord {
-        Coord { r, c }
+    fn part_two(trench_map: &Self::Input) -> Answer {
+        enhanced_lit_count(trench_map, 50)
     }
+}
 
-    pub fn read_adj(&self) -> impl Iterator<Item=Coord> {
-        let r = self.r;
-        let c = self.c;
-        (-1..=1).flat_map(move |dr| {
-            (-1..=1).map(move |dc| {
-                Coord::new(r + dr, c + dc)
-            })
-        })
+fn enhanced_lit_count(trench_map: &ImageEnhancer, times: usize) -> Answer {
+    let mut trench_map = trench_map.clone();
+    (0..times).for_each(|_| trench_map.enhance());
+    match trench_map.lit_count() {
+        Some(count) => count.into(),
+        None => Answer::from("infinity"),
     }
 }
 
+#[derive(Clone)]
 pub struct ImageEnhancer {
-    pixels: HashSet<Coord>,
+    // the infinite pixels outside of the image are its background
+    image: SparseGrid<bool>,
     enhancer: [bool; 512],
-    inverted: bool,
 }
 
 impl ImageEnhancer {
-    pub fn new(image: &Vec<&str>, enhance_string: &str) -> Self {
-        let pixels = image.iter().enumerate().flat_map(|(r, row)| {
-            row.chars().enumerate().filter_map(move |(c, char)| {
-                if char == '#' {
-                    Some(Coord::new(r.try_into().unwrap(), c.try_into().unwrap()))
-                } else {
-                    None
-                }
-            })
-        }).collect();
-
-        assert_eq!(512, enhance_string.chars().count());
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+
+        let enhance_string = lines.next()
+            .ok_or_else(|| ParseError::new(1, 1, "expected an enhancement string"))?;
+        let len = enhance_string.chars().count();
+        if len != 512 {
+            return Err(ParseError::at(input, enhance_string, format!("the enhancement string must have 512 characters, found {}", len)));
+        }
         let mut enhancer = [false; 512];
-        for (i, char) in enhance_string.chars().enumerate() {
-            enhancer[i] = match char {
-                '#' => true,
-                '.' => false,
-                _ => panic!("invalid character in enhance_string"),
-            };
+        for (i, (byte, char)) in enhance_string.char_indices().enumerate() {
+            enhancer[i] = Self::parse_pixel(char)
+                .ok_or_else(|| ParseError::at(input, &enhance_string[byte..], format!("invalid character `{}`", char)))?;
+        }
+
+        // the image follows a blank line
+        match lines.next() {
+            Some("") => (),
+            Some(line) => return Err(ParseError::at(input, line, "expected a blank line")),
+            None => return Err(ParseError::at(input, &input[input.len()..], "expected an image")),
         }
 
-        Self { pixels, enhancer, inverted: false }
+        let image = match lines.next() {
+            Some(first) => {
+                let image = &input[first.as_ptr() as usize - input.as_ptr() as usize..];
+                Grid::parse(image, |c| Self::parse_pixel(c).ok_or_else(|| format!("invalid character `{}`", c)))
+                    .map_err(|e| e.within(input, image))?
+            },
+            None => return Err(ParseError::at(input, &input[input.len()..], "expected an image")),
+        };
+
+        let image = image.iter()
+            .map(|(c, &lit)| (Point::new(c.col as i64, c.row as i64), lit))
+            .collect();
+
+        Ok(Self { image, enhancer })
+    }
+
+    fn parse_pixel(char: char) -> Option<bool> {
+        match char {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
     }
 
     pub fn enhance(&mut self) {
-        //  for each lit pixels:
-        //      for each pixel around the lit pixel:
-        //          construct the index from the block
-        //          insert the new lit pixel
-        //          memoize the result
-
-        let mut lit = HashSet::new();
-        let mut dark = HashSet::new();
-        let mut memo = HashSet::new();
-
-        for pixel in self.pixels.iter() {
-            for adj in pixel.read_adj() {
-                if !memo.contains(&adj) {
-                    let index: String = adj.read_adj()
-                    .map(|c| {
-                        match self.is_lit(c) {
-                            true => '1',
-                            false => '0',
-                        }
-                    }).collect();
-                    let index: usize = usize::from_str_radix(&index, 2).unwrap();
-                    if self.enhancer[index] {
-                        lit.insert(adj);
-                    } else {
-                        dark.insert(adj);
-                    }
-                    memo.insert(adj);
+        // every pixel in the background sees only background pixels
+        let background = if *self.image.background() { self.enhancer[511] } else { self.enhancer[0] };
+        let mut image = SparseGrid::new(background);
+
+        // the image grows by a pixel on every side, since those pixels can
+        // see into the image
+        if let Some((min, max)) = self.image.bounds() {
+            for y in min.y - 1..=max.y + 1 {
+                for x in min.x - 1..=max.x + 1 {
+                    let p = Point::new(x, y);
+                    image.insert(p, self.enhancer[self.index(p)]);
                 }
             }
         }
 
-        if self.inverted {
-            if !self.enhancer[511] {
-                self.pixels = lit;
-                self.inverted = false;
-            } else {
-                self.pixels = dark;
-            }
-        } else {
-            if self.enhancer[0] {
-                self.pixels = dark;
-                self.inverted = true;
-            } else {
-                self.pixels = lit;
-            }
-        }
+        self.image = image;
     }
 
-    fn is_lit(&self, c: Coord) -> bool {
-        self.inverted ^ self.pixels.contains(&c)
+    // the enhancement index for the pixel at `p`
+    fn index(&self, p: Point) -> usize {
+        (-1..=1).flat_map(|dy| (-1..=1).map(move |dx| Point::new(p.x + dx, p.y + dy)))
+            .fold(0, |index, p| (index << 1) | *self.image.get(p) as usize)
     }
 
     pub fn lit_count(&self) -> Option<usize> {
-        if self.inverted {
+        if *self.image.background() {
             None
         } else {
-            Some(self.pixels.len())
+            Some(self.image.iter().filter(|&(_, &lit)| lit).count())
         }
     }
 
     pub fn image(&self) -> String {
-        let mut result = String::new();
-        let (min, max) = self.bounds();
-
-        for r in min.r..=max.r {
-            for c in min.c..=max.c {
-                if self.is_lit(Coord::new(r, c)) {
-                    result.push('#');
-                } else {
-                    result.push('.');
-                }
-            }
-            result.push('\n');
-        }
-
-        String::from(result.trim())
-    }
-
-    fn bounds(&self) -> (Coord, Coord) {
-        if self.pixels.len() == 0 {
-            (Coord::new(0, 0), Coord::new(0, 0))
-        } else {
-            let min = self.pixels.iter().cloned().reduce(|a, b| {
-                Coord::new(std::cmp::min(a.r, b.r), std::cmp::min(a.c, b.c))
-            }).unwrap();
-            let max = self.pixels.iter().cloned().reduce(|a, b| {
-                Coord::new(std::cmp::max(a.r, b.r), std::cmp::max(a.c, b.c))
-            }).unwrap();
-
-            (min, max)
-        }
+        self.image.render(|&lit| if lit { '#' } else { '.' })
     }
 }
 
@@ -153,16 +126,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_coord_read_adj() {
-        let c = Coord::new(0, 0);
-        let expected = vec![
-            (-1, -1), (-1, 0), (-1, 1),
-            ( 0, -1), ( 0, 0), ( 0, 1),
-            ( 1, -1), ( 1, 0), ( 1, 1),
-        ].into_iter().map(|p| Coord::new(p.0, p.1));
-
-        for (expected, actual) in expected.zip(c.read_adj()) {
-            assert_eq!(expected, actual);
-        }
+    fn test_index() {
+        let enhancer = ImageEnhancer::parse(include_str!("../input/example.txt")).unwrap();
+
+        // the worked example from the puzzle, `...#...#.`
+        assert_eq!(34, enhancer.index(Point::new(2, 2)));
+        assert_eq!(0, enhancer.index(Point::new(9, -5)));
     }
 }
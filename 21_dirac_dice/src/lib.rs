@@ -1,4 +1,46 @@
 use std::collections::{HashMap};
+use regex::Regex;
+use common::{parse_at, Answer, Counter, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(21, "Dirac Dice", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    // each player's starting position
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_players(input)
+    }
+
+    fn part_one(players: &Self::Input) -> Answer {
+        play_deterministic(players.clone()).into()
+    }
+
+    fn part_two(players: &Self::Input) -> Answer {
+        let wins = play_dirac(players[0], players[1]);
+        let result = *wins.iter().map(|(_player, wins)| wins).max().unwrap();
+        result.into()
+    }
+}
+
+fn parse_players(input: &str) -> Result<Vec<u32>, ParseError> {
+    let regex = Regex::new(r"Player [0-9]+ starting position: (?P<position>[0-9]+)").unwrap();
+    let players = regex.captures_iter(input).map(|c| {
+        let position = c.name("position").unwrap().as_str();
+        match parse_at(input, position)? {
+            position @ 1..=10 => Ok(position),
+            _ => Err(ParseError::at(input, position, "starting positions must be from 1 to 10")),
+        }
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    if players.len() != 2 {
+        return Err(ParseError::new(1, 1, format!("expected 2 players, found {}", players.len())));
+    }
+
+    Ok(players)
+}
 
 pub fn play_deterministic(players: Vec<u32>) -> u32 {
     let mut die = DieDeterministic::new();
@@ -48,9 +90,6 @@ impl Iterator for DieDeterministic {
     }
 }
 
-mod counter;
-use counter::Counter;
-
 const ROLL_FREQS: [(u32, u128); 7] = [
     (3, 1),
     (4, 3),
@@ -103,7 +142,7 @@ impl DiracDiceState {
     }
 }
 
-pub fn play_dirac(p1: u32, p2: u32) -> Counter<Player> {
+pub fn play_dirac(p1: u32, p2: u32) -> Counter<Player, u128> {
     let start_state = DiracDiceState {
         p1: PlayerState {
             position: p1,
@@ -118,12 +157,12 @@ pub fn play_dirac(p1: u32, p2: u32) -> Counter<Player> {
     win_counts(start_state, &mut HashMap::new())
 }
 
-fn win_counts(state: DiracDiceState, memo: &mut HashMap<DiracDiceState, Counter<Player>>) -> Counter<Player> {
+fn win_counts(state: DiracDiceState, memo: &mut HashMap<DiracDiceState, Counter<Player, u128>>) -> Counter<Player, u128> {
     if memo.contains_key(&state) {
         memo.get(&state).unwrap().clone()
     } else {
-        let result: Counter<Player> = ROLL_FREQS.iter().flat_map(|&(roll, count)| {
-            let mut next_state = state.clone();
+        let result: Counter<Player, u128> = ROLL_FREQS.iter().map(|&(roll, count)| {
+            let mut next_state = state;
             let next_player = next_state.next_player_state();
 
             let new_position = ((((next_player.position) - 1) + roll) % 10) + 1;
@@ -131,21 +170,16 @@ fn win_counts(state: DiracDiceState, memo: &mut HashMap<DiracDiceState, Counter<
             if new_score >= 21 {
                 let mut c = Counter::new();
                 c.add_count(next_state.next, count);
-                c.into_iter()
+                c
             } else {
                 next_state.set_next_player_state(PlayerState {
                     position: new_position,
                     score: new_score,
                 });
                 next_state.toggle_next();
-                win_counts(next_state, memo);
-                let mut c = Counter::new();
-                for (player, win_count) in win_counts(next_state, memo).into_iter() {
-                    c.add_count(player, win_count * count);
-                }
-                c.into_iter()
+                win_counts(next_state, memo) * count
             }
-        }).collect();
+        }).sum();
 
         memo.insert(state, result.clone());
 
@@ -158,8 +192,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let players = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(739785), Solver::part_one(&players));
+        assert_eq!(Answer::Number(444356092776315), Solver::part_two(&players));
     }
 }
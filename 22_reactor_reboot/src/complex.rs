@@ -77,7 +77,7 @@ impl Cuboid {
     }
 
     pub fn size(&self) -> u128 {
-        ((self.e.x - self.s.x) as u128 * (self.e.y - self.s.y) as u128 * (self.e.z - self.s.z) as u128).try_into().unwrap()
+        (self.e.x - self.s.x) as u128 * (self.e.y - self.s.y) as u128 * (self.e.z - self.s.z) as u128
     }
 
     pub fn overlap(&self, other: &Self) -> Option<Self> {
@@ -85,10 +85,9 @@ impl Cuboid {
         let y = self.axis_overlap(other, Axis::Y);
         let z = self.axis_overlap(other, Axis::Z);
 
-        if x.is_some() && y.is_some() && z.is_some() {
-            Some(Self::from_pairs(x.unwrap(), y.unwrap(), z.unwrap()))
-        } else {
-            None
+        match (x, y, z) {
+            (Some(x), Some(y), Some(z)) => Some(Self::from_pairs(x, y, z)),
+            _ => None,
         }
     }
 
@@ -97,8 +96,6 @@ impl Cuboid {
 
         if this.0 < that.1 && this.1 > that.0 {
             Some((max(this.0, that.0), min(this.1, that.1)))
-        } else if that.0 < this.1 && that.1 > this.0 {
-            Some((max(this.0, that.0), min(this.1, that.1)))
         } else {
             None
         }
@@ -106,12 +103,7 @@ impl Cuboid {
 
     pub fn break_around(&self, other: &Self) -> Option<Vec<Self>> {
 
-        let overlap = self.overlap(other);
-        if overlap.is_none() {
-            return None;
-        }
-
-        let overlap = overlap.unwrap();
+        let overlap = self.overlap(other)?;
 
         let mut xyz_breaks: [Vec<i32>; 3] = [
             vec![self.s.x],
@@ -162,6 +154,12 @@ pub struct Reactor {
     non_overlapping: Vec<Cuboid>,
 }
 
+impl Default for Reactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Reactor {
     pub fn new() -> Self {
         Self {
@@ -439,7 +437,7 @@ mod tests {
         reactor.turn_on(Cuboid::from_ranges(6..10, 6..10, 6..10));
         reactor.turn_on(Cuboid::from_ranges(9..13, 9..13, 9..13));
 
-        let expected = (64 * 4) - (1 * 3);
+        let expected = (64 * 4) - 3;
 
         assert_eq!(expected, reactor.on_count());
     }
@@ -452,7 +450,7 @@ mod tests {
         reactor.turn_on(Cuboid::from_ranges(4..7, 0..1, 0..1));
         reactor.turn_on(Cuboid::from_ranges(6..9, 0..1, 0..1));
 
-        let expected = (3 * 4) - (1 * 3);
+        let expected = (3 * 4) - 3;
 
         assert_eq!(expected, reactor.on_count());
     }
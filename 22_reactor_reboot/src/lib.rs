@@ -4,3 +4,88 @@ pub use simple::Reactor as SimpleReactor;
 
 mod complex;
 pub use complex::{Reactor as ComplexReactor, Cuboid as ComplexCuboid};
+
+use std::ops::RangeInclusive;
+use regex::Regex;
+use common::{parse_at, Answer, Day, ParseError, Solution};
+
+pub const DAY: Day = Day::new::<Solver>(22, "Reactor Reboot", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+/// One step of the reboot: turn every cube in a cuboid on or off.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub on: bool,
+    pub x: RangeInclusive<i32>,
+    pub y: RangeInclusive<i32>,
+    pub z: RangeInclusive<i32>,
+}
+
+impl Solution for Solver {
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_steps(input)
+    }
+
+    fn part_one(steps: &Self::Input) -> Answer {
+        let mut reactor = SimpleReactor::new();
+
+        for step in steps {
+            let cuboid = SimpleCuboid::new(step.x.clone(), step.y.clone(), step.z.clone());
+            if cuboid.is_initialization() {
+                if step.on {
+                    reactor.turn_on(cuboid);
+                } else {
+                    reactor.turn_off(cuboid);
+                }
+            }
+        }
+
+        reactor.on_count().into()
+    }
+
+    fn part_two(steps: &Self::Input) -> Answer {
+        let mut reactor = ComplexReactor::new();
+
+        for step in steps {
+            let cuboid = ComplexCuboid::from_ranges(step.x.clone(), step.y.clone(), step.z.clone());
+            if step.on {
+                reactor.turn_on(cuboid);
+            } else {
+                reactor.turn_off(cuboid);
+            }
+        }
+
+        reactor.on_count().into()
+    }
+}
+
+fn parse_steps(input: &str) -> Result<Vec<Step>, ParseError> {
+    let regex = Regex::new(r"-?[0-9]+").unwrap();
+    input.lines().map(|line| {
+        let on = match line.split_whitespace().next() {
+            Some("on") => true,
+            Some("off") => false,
+            _ => return Err(ParseError::at(input, line, "a step must start with `on` or `off`")),
+        };
+
+        let nums = regex.find_iter(line)
+            .map(|num| parse_at(input, num.as_str()))
+            .collect::<Result<Vec<i32>, _>>()?;
+        if nums.len() != 6 {
+            return Err(ParseError::at(input, line, format!("expected 6 bounds, found {}", nums.len())));
+        }
+        if nums[0] > nums[1] || nums[2] > nums[3] || nums[4] > nums[5] {
+            return Err(ParseError::at(input, line, "ranges must not end before they start"));
+        }
+
+        Ok(Step {
+            on,
+            x: nums[0]..=nums[1],
+            y: nums[2]..=nums[3],
+            z: nums[4]..=nums[5],
+        })
+    }).collect()
+}
@@ -47,6 +47,12 @@ pub struct Reactor {
     on: HashSet<Coord>,
 }
 
+impl Default for Reactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Reactor {
     pub fn new() -> Self {
         Reactor { on: HashSet::new() }
@@ -79,8 +85,6 @@ impl Reactor {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn it_works() {
         let result = 2 + 2;
@@ -1,10 +1,66 @@
 use std::cmp::{max, min};
-use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
-use serde::Serialize;
+use common::{Answer, Day, ParseError, Solution};
+use common::search::{self, Path, Successors};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub const DAY: Day = Day::new::<Solver>(23, "Amphipod", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Vec<Vec<Amphipod>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rooms(input)
+    }
+
+    fn part_one(rooms: &Self::Input) -> Answer {
+        let burrow = Burrow::new(rooms);
+
+        let path = burrow.min_energy().unwrap();
+
+        // println!("steps: {}", path.states.len());
+
+        // for (i, state) in path.states.iter().enumerate() {
+        //     println!("\nStep {}:\n{}", i, state);
+        // }
+
+        path.cost.into()
+    }
+
+    fn part_two(rooms: &Self::Input) -> Answer {
+        let extensions = [
+            ['D', 'D',],
+            ['C', 'B',],
+            ['B', 'A',],
+            ['A', 'C',],
+        ];
+        let rooms: Vec<_> = rooms.iter().zip(extensions)
+            .map(|(r, e)| vec![r[0], Amphipod::from_char(e[0]).unwrap(), Amphipod::from_char(e[1]).unwrap(), r[1]])
+            .collect();
+
+        let burrow = Burrow::new(&rooms);
+
+        burrow.min_energy().unwrap().cost.into()
+    }
+}
+
+fn parse_rooms(input: &str) -> Result<Vec<Vec<Amphipod>>, ParseError> {
+    // just get all the letters
+    let amphipods: Vec<_> = input.chars()
+        .filter_map(Amphipod::from_char)
+        .collect();
+    if amphipods.len() != 8 {
+        return Err(ParseError::new(1, 1, format!("expected 8 amphipods, found {}", amphipods.len())));
+    }
+
+    Ok((0..4)
+        .map(|i| vec![amphipods[i], amphipods[i + 4]])
+        .collect())
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Amphipod {
     Amber,
     Bronze,
@@ -38,7 +94,7 @@ impl fmt::Display for Amphipod {
 use hallway::Hallway;
 use room::Room;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Burrow {
     hallway: Hallway,
     // Smart pointer; Room is immutable, so we want to share the same room
@@ -121,73 +177,40 @@ impl Burrow {
         }
     }
 
-    pub fn min_energy(&self) -> Option<(Vec<Burrow>, u32)> {
-        let mut memo = HashMap::new();
-        self.min_energy_rec(&mut memo, 0, None)
-            .map(|(states, energy)| (states.into_iter().rev().collect(), energy))
+    /// The cheapest way to get every amphipod home, as the burrow after each
+    /// move and the total energy spent.
+    pub fn min_energy(&self) -> Option<Path<Burrow, u32>> {
+        search::dijkstra(&Moves, self.clone(), Burrow::is_solved)
     }
 
-    fn min_energy_rec(&self, memo: &mut HashMap<Vec<u8>, u32>, energy: u32, known_min: Option<u32>) -> Option<(Vec<Burrow>, u32)> {
-        // update memo, return early if this state has already been reached with less energy
-        let burrow_serialized = serde_json::to_vec(self).unwrap();
-        if memo.contains_key(&burrow_serialized) && *memo.get(&burrow_serialized).unwrap() <= energy {
-            return None;
-        } else {
-            memo.insert(burrow_serialized, energy);
-        }
-
-        if self.is_solved() {
-            return Some((vec![self.clone()], energy));
-        }
+    // every burrow one move away, with the energy that move takes
+    fn moves(&self) -> Vec<(Burrow, u32)> {
+        let mut moves = Vec::new();
 
-        let mut solutions = Vec::new();
-        let mut known_min = known_min;
-
-        // attempt to move amphipods from hallways to rooms
+        // move amphipods from hallways to rooms
         for (space, amphipod) in self.hallway.occupied() {
-            if self.hallway.can_remove_to(space, amphipod) && self.room(amphipod).can_insert(amphipod).is_some() {
+            if self.hallway.can_remove_to(space, amphipod) {
                 if let Some(depth) = self.room(amphipod).can_insert(amphipod) {
-                    let (added_energy, next_burrow) = self.hallway_to_room(space, amphipod, depth);
-                    if known_min.is_some() && energy + added_energy > known_min.unwrap() {
-                        continue;
-                    }
-                    if let Some(solution) = next_burrow.min_energy_rec(memo, energy + added_energy, known_min) {
-                        if known_min.is_none() || solution.1 < known_min.unwrap() {
-                            known_min = Some(solution.1);
-                        }
-                        solutions.push(solution);
-                    }
+                    let (energy, next_burrow) = self.hallway_to_room(space, amphipod, depth);
+                    moves.push((next_burrow, energy));
                 }
             }
         }
 
-        // attempt to move amphipods from rooms to hallways
+        // move amphipods from rooms to hallways
         for room in self.rooms.iter() {
             for depth in 0..self.room_size {
                 if room.can_remove(depth) {
                     let native = room.native();
                     for space in self.hallway.reachable_from(native) {
-                        let (added_energy, next_burrow) = self.room_to_hallway(native, depth, space);
-                        if known_min.is_some() && energy + added_energy > known_min.unwrap() {
-                            continue;
-                        }
-                        if let Some(solution) = next_burrow.min_energy_rec(memo, energy + added_energy, known_min) {
-                            if known_min.is_none() || solution.1 < known_min.unwrap() {
-                                known_min = Some(solution.1);
-                            }
-                            solutions.push(solution);
-                        }
+                        let (energy, next_burrow) = self.room_to_hallway(native, depth, space);
+                        moves.push((next_burrow, energy));
                     }
                 }
             }
         }
 
-        // take minimum of gathered energies
-        let min_solution = solutions.into_iter().min_by(|x, y| x.1.cmp(&y.1));
-        min_solution.map(|(mut states, energy)| {
-            states.push(self.clone());
-            (states, energy)
-        })
+        moves
     }
 
     fn is_solved(&self) -> bool {
@@ -209,6 +232,18 @@ impl Burrow {
     }
 }
 
+// the moves an amphipod can make, as a graph of burrows
+struct Moves;
+
+impl Successors for Moves {
+    type State = Burrow;
+    type Cost = u32;
+
+    fn successors(&self, burrow: &Burrow) -> impl IntoIterator<Item = (Burrow, u32)> {
+        burrow.moves()
+    }
+}
+
 impl fmt::Display for Burrow {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut result = String::from("#############\n#");
@@ -245,11 +280,10 @@ impl fmt::Display for Burrow {
 
 mod hallway {
     use std::cmp::{min, max};
-    use serde::Serialize;
 
     use crate::Amphipod;
 
-    #[derive(Debug, Copy, Clone, Serialize)]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
     pub struct Hallway {
         spaces: [Option<Amphipod>; 11],
     }
@@ -268,7 +302,7 @@ mod hallway {
         pub fn insert_from_room(&self, amphipod: Amphipod, from: Amphipod, to: usize) -> Self {
             assert!(self.can_insert_from(from, to), "insertion path is blocked");
 
-            let mut new_spaces = self.spaces.clone();
+            let mut new_spaces = self.spaces;
             new_spaces[to].replace(amphipod);
             let hallway = Self {
                 spaces: new_spaces,
@@ -280,7 +314,7 @@ mod hallway {
         pub fn remove_to_room(&self, from: usize, to: Amphipod) -> Self {
             assert!(self.can_remove_to(from, to), "removal path is blocked");
 
-            let mut new_spaces = self.spaces.clone();
+            let mut new_spaces = self.spaces;
             new_spaces[from].take();
             let hallway = Self {
                 spaces: new_spaces,
@@ -351,11 +385,11 @@ mod hallway {
             // })
 
             // not a huge fan of this... would like something more like the above
-            (0..start).rev().into_iter()
-            .filter(|i| !ROOM_ENTRANCES.contains(&i)).take_while(|i| !self.spaces[*i].is_some())
+            (0..start).rev()
+            .filter(|i| !ROOM_ENTRANCES.contains(i)).take_while(|i| self.spaces[*i].is_none())
             .chain(
-                (start+1..self.spaces.len()).into_iter()
-                .filter(|i| !ROOM_ENTRANCES.contains(&i)).take_while(|i| !self.spaces[*i].is_some())
+                (start+1..self.spaces.len())
+                .filter(|i| !ROOM_ENTRANCES.contains(i)).take_while(|i| self.spaces[*i].is_none())
             )
         }
 
@@ -373,9 +407,7 @@ mod hallway {
 mod room {
     use crate::Amphipod;
 
-    use serde::Serialize;
-
-    #[derive(Debug, Clone, Serialize)]
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub struct Room {
         size: usize,
         // who should live in the pod
@@ -389,7 +421,7 @@ mod room {
             let room = Self {
                 size: occupants.len(),
                 native,
-                occupants: occupants.into_iter().map(|&a| Some(a)).collect(),
+                occupants: occupants.iter().map(|&a| Some(a)).collect(),
             };
             room.check_rep();
             room
@@ -442,8 +474,8 @@ mod room {
         }
 
         pub fn _positions(&self, amphipod: Amphipod) -> Vec<usize> {
-            (0..self.size).into_iter()
-                .filter(|i| self.occupant(*i).map_or(false, |a| a == amphipod))
+            (0..self.size)
+                .filter(|i| self.occupant(*i) == Some(amphipod))
                 .collect()
         }
 
@@ -492,7 +524,7 @@ mod room {
 
         fn is_native(&self, depth: usize) -> bool {
             self.check_depth(depth);
-            self.occupant(depth).map_or(false, |a| a == self.native)
+            self.occupant(depth) == Some(self.native)
         }
 
         fn is_unoccupied(&self, depth: usize) -> bool {
@@ -513,8 +545,7 @@ mod tests {
 
     #[test]
     fn burrow_example_small() {
-        let burrow = Burrow::new(&vec![
-            vec! [
+        let burrow = Burrow::new(&[vec! [
                 Amphipod::Bronze,
                 Amphipod::Amber,
             ],
@@ -529,18 +560,16 @@ mod tests {
             vec![
                 Amphipod::Desert,
                 Amphipod::Amber,
-            ],
-        ]);
+            ]]);
 
         let expected = 12521;
 
-        assert_eq!(expected, burrow.min_energy().unwrap().1);
+        assert_eq!(expected, burrow.min_energy().unwrap().cost);
     }
 
     #[test]
     fn burrow_example_large() {
-        let burrow = Burrow::new(&vec![
-            vec! [
+        let burrow = Burrow::new(&[vec! [
                 Amphipod::Bronze,
                 Amphipod::Desert,
                 Amphipod::Desert,
@@ -563,11 +592,10 @@ mod tests {
                 Amphipod::Amber,
                 Amphipod::Copper,
                 Amphipod::Amber,
-            ],
-        ]);
+            ]]);
 
         let expected = 44169;
 
-        assert_eq!(expected, burrow.min_energy().unwrap().1);
+        assert_eq!(expected, burrow.min_energy().unwrap().cost);
     }
 }
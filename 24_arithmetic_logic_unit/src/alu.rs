@@ -1,11 +1,9 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
 use std::vec::IntoIter;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct MemoEntry {
-    block: usize,
-    input: Reg,
-    z: Reg,
-}
+use common::ParseError;
 
 #[derive(Debug, Clone)]
 pub struct Program {
@@ -13,15 +11,16 @@ pub struct Program {
 }
 
 impl Program {
-    pub fn parse(code: &str) -> Self {
-        Self {
-            instructions: code.lines().map(|op| Op::from_str(op)).collect(),
-        }
+    pub fn parse(code: &str) -> Result<Self, ParseError> {
+        let instructions = code.lines()
+            .map(|line| line.parse::<Op>().map_err(|e| e.within(code, line)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { instructions })
     }
 
     pub fn new(instructions: &[Op]) -> Self {
         Self {
-            instructions: instructions.iter().copied().collect()
+            instructions: instructions.to_vec()
         }
     }
 
@@ -29,6 +28,18 @@ impl Program {
         self.instructions.iter()
     }
 
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Op> {
+        self.instructions.get(index)
+    }
+
     pub fn into_blocks(&self) -> Vec<Self> {
         let mut result = Vec::new();
 
@@ -38,12 +49,12 @@ impl Program {
                 if let Op::Inp(_) = op {
                     result.push(block.clone());
                     block = Vec::new();
-                    block.push(op.clone());
+                    block.push(*op);
                 } else {
-                    block.push(op.clone());
+                    block.push(*op);
                 }
             } else {
-                block.push(op.clone());
+                block.push(*op);
             }
         }
         result.push(block.clone());
@@ -64,6 +75,11 @@ pub enum Op {
 
 pub type Reg = i64;
 
+// where register `var` goes in `[w, x, y, z]`
+pub(crate) fn index(var: char) -> usize {
+    "wxyz".find(var).unwrap_or_else(|| panic!("bad variable '{}'", var))
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Arg {
     Var(char),
@@ -71,32 +87,91 @@ pub enum Arg {
 }
 
 impl Arg {
-    pub fn arg(s: &str) -> Self {
+    // `s` must be a slice of `line`, so errors can point at it
+    pub fn any(line: &str, s: &str) -> Result<Self, ParseError> {
         if let Ok(num) = s.parse::<Reg>() {
-            Arg::Num(num)
+            Ok(Arg::Num(num))
         } else {
-            Arg::Var(s.chars().next().unwrap())
+            Arg::var(line, s)
         }
     }
 
-    pub fn var(s: &str) -> Self {
-        assert!(s.parse::<Reg>().is_err());
-        Arg::Var(s.chars().next().unwrap())
+    pub fn var(line: &str, s: &str) -> Result<Self, ParseError> {
+        match s {
+            "w" | "x" | "y" | "z" => Ok(Arg::Var(s.chars().next().unwrap())),
+            _ => Err(ParseError::at(line, s, format!("`{}` is not a register", s))),
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let s: Vec<_> = line.split_whitespace().collect();
+        let expected = match s.first() {
+            Some(&"inp") => 2,
+            Some(&("add" | "mul" | "div" | "mod" | "eql")) => 3,
+            Some(op) => return Err(ParseError::at(line, op, format!("invalid operation `{}`", op))),
+            None => return Err(ParseError::new(1, 1, "missing operation")),
+        };
+        if s.len() != expected {
+            return Err(ParseError::at(line, s[0], format!("`{}` takes {} arguments", s[0], expected - 1)));
+        }
+
+        Ok(match s[0] {
+            "inp" => Op::Inp(Arg::var(line, s[1])?),
+            "add" => Op::Add(Arg::var(line, s[1])?, Arg::any(line, s[2])?),
+            "mul" => Op::Mul(Arg::var(line, s[1])?, Arg::any(line, s[2])?),
+            "div" => Op::Div(Arg::var(line, s[1])?, Arg::any(line, s[2])?),
+            "mod" => Op::Mod(Arg::var(line, s[1])?, Arg::any(line, s[2])?),
+            _ => Op::Eql(Arg::var(line, s[1])?, Arg::any(line, s[2])?),
+        })
+    }
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Arg::Var(var) => write!(f, "{}", var),
+            Arg::Num(num) => write!(f, "{}", num),
+        }
     }
 }
 
 impl Op {
-    pub fn from_str(s: &str) -> Self {
-        let s: Vec<_> = s.split_whitespace().collect();
-        match s[0] {
-            "inp" => Op::Inp(Arg::var(s[1])),
-            "add" => Op::Add(Arg::var(s[1]), Arg::arg(s[2])),
-            "mul" => Op::Mul(Arg::var(s[1]), Arg::arg(s[2])),
-            "div" => Op::Div(Arg::var(s[1]), Arg::arg(s[2])),
-            "mod" => Op::Mod(Arg::var(s[1]), Arg::arg(s[2])),
-            "eql" => Op::Eql(Arg::var(s[1]), Arg::arg(s[2])),
-            _ => panic!("invalid operation")
+    /// The register the instruction writes to.
+    pub fn var(&self) -> char {
+        let (Op::Inp(var) | Op::Add(var, _) | Op::Mul(var, _) | Op::Div(var, _) | Op::Mod(var, _) | Op::Eql(var, _)) = self;
+        match var {
+            Arg::Var(var) => *var,
+            Arg::Num(num) => panic!("'{}' is not variable", num),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::Inp(var) => write!(f, "inp {}", var),
+            Op::Add(var, arg) => write!(f, "add {} {}", var, arg),
+            Op::Mul(var, arg) => write!(f, "mul {} {}", var, arg),
+            Op::Div(var, arg) => write!(f, "div {} {}", var, arg),
+            Op::Mod(var, arg) => write!(f, "mod {} {}", var, arg),
+            Op::Eql(var, arg) => write!(f, "eql {} {}", var, arg),
+        }
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, op) in self.instructions.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", op)?;
         }
+        Ok(())
     }
 }
 
@@ -111,7 +186,7 @@ pub struct ALU {
 impl ALU {
     pub fn new(input: &[Reg]) -> Self {
         Self {
-            input: input.iter().copied().collect::<Vec<_>>().into_iter(),
+            input: Vec::from(input).into_iter(),
             w: 0,
             x: 0,
             y: 0,
@@ -119,10 +194,12 @@ impl ALU {
         }
     }
 
-    pub fn run(&mut self, program: &Program) {
-        for op in program.instructions() {
-            self.op(op);
+    /// Run the whole program, returning the registers it ends with.
+    pub fn run(&mut self, program: &Program) -> Result<Registers, AluError> {
+        for (index, op) in program.instructions().enumerate() {
+            self.op(op).map_err(|kind| AluError { index, kind })?;
         }
+        Ok(self.state())
     }
 
     pub fn set_state(&mut self, state: &Registers) {
@@ -132,20 +209,18 @@ impl ALU {
         self.z = state.z;
     }
 
-    fn op(&mut self, op: &Op) {
-        match op {
-            Op::Inp(var) => *self.var(var) = self.next_input(),
-            Op::Add(var, arg) => *self.var(var) = self.val(var) + self.val(arg),
-            Op::Mul(var, arg) => *self.var(var) = self.val(var) * self.val(arg),
-            Op::Div(var, arg) => *self.var(var) = self.val(var) / self.val(arg),
-            Op::Mod(var, arg) => *self.var(var) = self.val(var) % self.val(arg),
-            Op::Eql(var, arg) => *self.var(var) =  if self.val(var) == self.val(arg) { 1 } else { 0 },
-        }
-    }
-
-    fn next_input(&mut self) -> Reg {
-        // this should probably utilize a Result
-        self.input.next().unwrap_or_else(|| panic!("not enough input"))
+    /// Run a single instruction. If it fails, the registers are left as
+    /// they were.
+    pub fn op(&mut self, op: &Op) -> Result<(), AluErrorKind> {
+        *self.var(&Arg::Var(op.var())) = match op {
+            Op::Inp(_) => self.input.next().ok_or(AluErrorKind::MissingInput)?,
+            Op::Add(var, arg) => add(self.val(var), self.val(arg))?,
+            Op::Mul(var, arg) => multiply(self.val(var), self.val(arg))?,
+            Op::Div(var, arg) => divide(self.val(var), self.val(arg))?,
+            Op::Mod(var, arg) => remainder(self.val(var), self.val(arg))?,
+            Op::Eql(var, arg) => if self.val(var) == self.val(arg) { 1 } else { 0 },
+        };
+        Ok(())
     }
 
     fn val(&self, arg: &Arg) -> Reg {
@@ -200,6 +275,66 @@ impl ALU {
     }
 }
 
+pub(crate) fn add(a: Reg, b: Reg) -> Result<Reg, AluErrorKind> {
+    a.checked_add(b).ok_or(AluErrorKind::Overflow)
+}
+
+pub(crate) fn multiply(a: Reg, b: Reg) -> Result<Reg, AluErrorKind> {
+    a.checked_mul(b).ok_or(AluErrorKind::Overflow)
+}
+
+pub(crate) fn divide(a: Reg, b: Reg) -> Result<Reg, AluErrorKind> {
+    if b == 0 {
+        return Err(AluErrorKind::DivisionByZero);
+    }
+    a.checked_div(b).ok_or(AluErrorKind::Overflow)
+}
+
+pub(crate) fn remainder(a: Reg, b: Reg) -> Result<Reg, AluErrorKind> {
+    if a < 0 || b <= 0 {
+        return Err(AluErrorKind::InvalidModulo(a, b));
+    }
+    a.checked_rem(b).ok_or(AluErrorKind::Overflow)
+}
+
+/// Why an instruction could not be run.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum AluErrorKind {
+    /// An `inp` with no input left.
+    MissingInput,
+    DivisionByZero,
+    /// A `mod` of a negative number, or by one that is not positive.
+    InvalidModulo(Reg, Reg),
+    /// A result too large for a register, like `MIN / -1`.
+    Overflow,
+}
+
+/// An instruction that could not be run, and the index it is at.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct AluError {
+    pub index: usize,
+    pub kind: AluErrorKind,
+}
+
+impl fmt::Display for AluErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AluErrorKind::MissingInput => write!(f, "no input left"),
+            AluErrorKind::DivisionByZero => write!(f, "division by zero"),
+            AluErrorKind::InvalidModulo(a, b) => write!(f, "invalid modulo {} % {}", a, b),
+            AluErrorKind::Overflow => write!(f, "overflow"),
+        }
+    }
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "instruction {}: {}", self.index, self.kind)
+    }
+}
+
+impl Error for AluError {}
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub struct Registers {
     pub w: Reg,
@@ -208,16 +343,57 @@ pub struct Registers {
     pub z: Reg,
 }
 
+impl Registers {
+    /// The value of register `var`, one of `w`, `x`, `y` or `z`.
+    pub fn get(&self, var: char) -> Option<Reg> {
+        match var {
+            'w' => Some(self.w),
+            'x' => Some(self.x),
+            'y' => Some(self.y),
+            'z' => Some(self.z),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "w={} x={} y={} z={}", self.w, self.x, self.y, self.z)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn op_parse() {
-        let op = Op::from_str("add w 1");
+        let op = "add w 1".parse::<Op>().unwrap();
         assert_eq!(Op::Add(Arg::Var('w'), Arg::Num(1)), op);
     }
 
+    #[test]
+    fn op_display() {
+        for line in ["inp w", "add x -3", "eql z y"] {
+            assert_eq!(line, line.parse::<Op>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn program_display() {
+        let code = "inp w\nmul x 0\ndiv z -26";
+        assert_eq!(code, Program::parse(code).unwrap().to_string());
+    }
+
+    #[test]
+    fn program_parse_invalid() {
+        let error = Program::parse("inp w\nadd q 1").unwrap_err();
+        assert_eq!((2, 5), (error.line(), error.column()));
+
+        let error = Program::parse("inp w\nmul x\n").unwrap_err();
+        assert_eq!((2, 1), (error.line(), error.column()));
+    }
+
     #[test]
     fn test_alu_basic() {
         // z = (z*3 == x)
@@ -226,15 +402,29 @@ mod tests {
             "inp x",
             "mul z 3",
             "eql z x",
-        ].into_iter().map(|op| Op::from_str(op)).collect::<Vec<_>>());
+        ].into_iter().map(|op| op.parse().unwrap()).collect::<Vec<_>>());
 
         for test in [(vec![3, 9], 1), (vec![2, 7], 0)] {
             let mut alu = ALU::new(&test.0);
-            alu.run(&program);
+            alu.run(&program).unwrap();
             assert_eq!(test.1, alu.z())
         }
     }
 
+    #[test]
+    fn test_alu_errors() {
+        let error = |code: &str, input: &[Reg]| ALU::new(input).run(&Program::parse(code).unwrap()).unwrap_err();
+
+        assert_eq!(AluError { index: 1, kind: AluErrorKind::DivisionByZero }, error("inp w\ndiv w x", &[3]));
+        assert_eq!(AluError { index: 2, kind: AluErrorKind::InvalidModulo(-3, 2) }, error("inp w\nmul w -1\nmod w 2", &[3]));
+        assert_eq!(AluError { index: 1, kind: AluErrorKind::InvalidModulo(3, 0) }, error("inp w\nmod w x", &[3]));
+        assert_eq!(AluError { index: 1, kind: AluErrorKind::MissingInput }, error("inp w\ninp x", &[3]));
+        assert_eq!(AluError { index: 1, kind: AluErrorKind::Overflow }, error("add w -9223372036854775808\ndiv w -1", &[]));
+        assert_eq!(AluError { index: 1, kind: AluErrorKind::Overflow }, error("add w 9223372036854775807\nmul w 2", &[]));
+        assert_eq!(AluError { index: 2, kind: AluErrorKind::Overflow }, error("add w 9223372036854775807\ninp x\nadd w x", &[1]));
+        assert_eq!("instruction 1: division by zero", error("inp w\ndiv w x", &[3]).to_string());
+    }
+
     #[test]
     fn test_alu_binary() {
         // input num -> binary
@@ -250,7 +440,7 @@ mod tests {
             "mod x 2",
             "div w 2",
             "mod w 2",
-        ].into_iter().map(|op| Op::from_str(op)).collect::<Vec<_>>());
+        ].into_iter().map(|op| op.parse().unwrap()).collect::<Vec<_>>());
 
         for test in [
             (vec![0], (0, 0, 0, 0)),
@@ -266,7 +456,7 @@ mod tests {
 
         ] {
             let mut alu = ALU::new(&test.0);
-            alu.run(&program);
+            alu.run(&program).unwrap();
             assert_eq!(test.1.0, alu.w());
             assert_eq!(test.1.1, alu.x());
             assert_eq!(test.1.2, alu.y());
@@ -0,0 +1,168 @@
+//! Step through an ALU program, watching its registers change.
+//!
+//! Usage: `alu-debug PROGRAM [INPUT]`, where `INPUT` is either a string of
+//! digits, one per `inp`, or a comma separated list of numbers. Type `help`
+//! at the prompt for the commands.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use arithmetic_logic_unit::{Breakpoint, Debugger, Program, Reg, Stop};
+
+const HELP: &str = "\
+step [N]        run the next N instructions (default 1)
+continue        run until a breakpoint or the end of the program
+break INDEX     stop before the instruction at INDEX
+break COND      stop when COND becomes true, like `z == 0` or `x > 3`
+delete N        remove breakpoint N
+breaks          list the breakpoints
+regs            show the registers
+list [N]        show N instructions around the next one (default 5)
+trace [N]       show the last N instructions run (default 10)
+quit            leave";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("usage: alu-debug PROGRAM [INPUT]");
+        process::exit(2);
+    }
+
+    let code = fs::read_to_string(&args[0]).unwrap_or_else(|e| {
+        eprintln!("could not read {}: {}", args[0], e);
+        process::exit(1);
+    });
+    let program = Program::parse(&code).unwrap_or_else(|e| {
+        eprintln!("{}: {}", args[0], e);
+        process::exit(1);
+    });
+    let input = parse_input(args.get(1).map_or("", String::as_str)).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    });
+
+    let mut debugger = Debugger::new(&program, &input);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+        print!("(alu) ");
+        io::stdout().flush().unwrap();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, rest) = match words.split_first() {
+            Some((command, rest)) => (*command, rest.join(" ")),
+            None => continue,
+        };
+
+        match command {
+            "s" | "step" => {
+                let steps = match count(&rest, 1) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        println!("{}", e);
+                        continue;
+                    },
+                };
+                for _ in 0..steps {
+                    match debugger.step() {
+                        Ok(Some(entry)) => println!("{}", entry),
+                        Ok(None) => {
+                            println!("the program has finished");
+                            break;
+                        },
+                        Err(e) => {
+                            println!("{}", e);
+                            break;
+                        },
+                    }
+                }
+            },
+            "c" | "continue" => {
+                match debugger.resume() {
+                    Stop::Breakpoint(i) => println!("breakpoint {}: {}", i, debugger.breakpoints()[i]),
+                    Stop::Error(e) => println!("{}", e),
+                    Stop::Finished => println!("the program has finished"),
+                }
+                if let Some(entry) = debugger.trace().last() {
+                    println!("{}", entry);
+                }
+            },
+            "b" | "break" => {
+                let breakpoint = match rest.parse::<usize>() {
+                    Ok(index) => Ok(Breakpoint::Instruction(index)),
+                    Err(_) => rest.parse().map(Breakpoint::Condition),
+                };
+                match breakpoint {
+                    Ok(breakpoint) => println!("breakpoint {}: {}", debugger.add_breakpoint(breakpoint), breakpoint),
+                    Err(e) => println!("{}", e),
+                }
+            },
+            "d" | "delete" => match rest.parse().ok().and_then(|i| debugger.remove_breakpoint(i)) {
+                Some(breakpoint) => println!("removed {}", breakpoint),
+                None => println!("no breakpoint `{}`", rest),
+            },
+            "breaks" => {
+                for (i, breakpoint) in debugger.breakpoints().iter().enumerate() {
+                    println!("{}: {}", i, breakpoint);
+                }
+            },
+            "r" | "regs" => println!("pc={} {}", debugger.pc(), debugger.registers()),
+            "l" | "list" => {
+                let around = match count(&rest, 5) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        println!("{}", e);
+                        continue;
+                    },
+                };
+                let start = debugger.pc().saturating_sub(around);
+                for index in start..(debugger.pc() + around + 1).min(program.len()) {
+                    let marker = if index == debugger.pc() { '>' } else { ' ' };
+                    println!("{} {:>4}  {}", marker, index, program.get(index).unwrap());
+                }
+            },
+            "t" | "trace" => {
+                let last = match count(&rest, 10) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        println!("{}", e);
+                        continue;
+                    },
+                };
+                let trace = debugger.trace();
+                for entry in &trace[trace.len().saturating_sub(last)..] {
+                    println!("{}", entry);
+                }
+            },
+            "h" | "help" => println!("{}", HELP),
+            "q" | "quit" => break,
+            _ => println!("unknown command `{}`; try `help`", command),
+        }
+    }
+}
+
+// a command's count argument, or `default` if it has none
+fn count(rest: &str, default: usize) -> Result<usize, String> {
+    if rest.is_empty() {
+        return Ok(default);
+    }
+    rest.parse().map_err(|_| format!("`{}` is not a count", rest))
+}
+
+fn parse_input(input: &str) -> Result<Vec<Reg>, String> {
+    if input.contains(',') {
+        input.split(',')
+            .map(|num| num.trim().parse().map_err(|_| format!("`{}` is not a number", num.trim())))
+            .collect()
+    } else {
+        input.chars()
+            .map(|c| c.to_digit(10).map(Reg::from).ok_or_else(|| format!("`{}` is not a digit", c)))
+            .collect()
+    }
+}
@@ -0,0 +1,119 @@
+use crate::alu::{add, divide, index, multiply, remainder, AluError, AluErrorKind, Arg, Op, Program, Reg, Registers};
+
+// an `Op` with its registers turned into indexes into `[w, x, y, z]`, and
+// split on whether the argument is a register or a number
+#[derive(Debug, Copy, Clone)]
+enum Instruction {
+    Inp(usize),
+    Add(usize, usize),
+    AddNum(usize, Reg),
+    Mul(usize, usize),
+    MulNum(usize, Reg),
+    Div(usize, usize),
+    DivNum(usize, Reg),
+    Mod(usize, usize),
+    ModNum(usize, Reg),
+    Eql(usize, usize),
+    EqlNum(usize, Reg),
+}
+
+/// A program turned into instructions that are quicker to run than the
+/// `Op`s `ALU::run` works through.
+#[derive(Debug, Clone)]
+pub struct CompiledProgram {
+    instructions: Vec<Instruction>,
+}
+
+impl CompiledProgram {
+    pub fn new(program: &Program) -> Self {
+        let instructions = program.instructions().map(|op| {
+            let var = index(op.var());
+            match *op {
+                Op::Inp(_) => Instruction::Inp(var),
+                Op::Add(_, Arg::Var(arg)) => Instruction::Add(var, index(arg)),
+                Op::Add(_, Arg::Num(num)) => Instruction::AddNum(var, num),
+                Op::Mul(_, Arg::Var(arg)) => Instruction::Mul(var, index(arg)),
+                Op::Mul(_, Arg::Num(num)) => Instruction::MulNum(var, num),
+                Op::Div(_, Arg::Var(arg)) => Instruction::Div(var, index(arg)),
+                Op::Div(_, Arg::Num(num)) => Instruction::DivNum(var, num),
+                Op::Mod(_, Arg::Var(arg)) => Instruction::Mod(var, index(arg)),
+                Op::Mod(_, Arg::Num(num)) => Instruction::ModNum(var, num),
+                Op::Eql(_, Arg::Var(arg)) => Instruction::Eql(var, index(arg)),
+                Op::Eql(_, Arg::Num(num)) => Instruction::EqlNum(var, num),
+            }
+        }).collect();
+
+        Self { instructions }
+    }
+
+    /// Run the program from `registers`, reading `input` in order, and
+    /// return the registers it ends with.
+    pub fn run(&self, registers: &Registers, input: &[Reg]) -> Result<Registers, AluError> {
+        let mut regs = [registers.w, registers.x, registers.y, registers.z];
+        let mut input = input.iter();
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let error = |kind| AluError { index, kind };
+            match *instruction {
+                Instruction::Inp(var) => regs[var] = *input.next().ok_or(error(AluErrorKind::MissingInput))?,
+                Instruction::Add(var, arg) => regs[var] = add(regs[var], regs[arg]).map_err(error)?,
+                Instruction::AddNum(var, num) => regs[var] = add(regs[var], num).map_err(error)?,
+                Instruction::Mul(var, arg) => regs[var] = multiply(regs[var], regs[arg]).map_err(error)?,
+                Instruction::MulNum(var, num) => regs[var] = multiply(regs[var], num).map_err(error)?,
+                Instruction::Div(var, arg) => regs[var] = divide(regs[var], regs[arg]).map_err(error)?,
+                Instruction::DivNum(var, num) => regs[var] = divide(regs[var], num).map_err(error)?,
+                Instruction::Mod(var, arg) => regs[var] = remainder(regs[var], regs[arg]).map_err(error)?,
+                Instruction::ModNum(var, num) => regs[var] = remainder(regs[var], num).map_err(error)?,
+                Instruction::Eql(var, arg) => regs[var] = (regs[var] == regs[arg]) as Reg,
+                Instruction::EqlNum(var, num) => regs[var] = (regs[var] == num) as Reg,
+            }
+        }
+
+        let [w, x, y, z] = regs;
+        Ok(Registers { w, x, y, z })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alu::ALU;
+
+    #[test]
+    fn test_run() {
+        // z = (z*3 == x), from registers that are not all 0
+        let program = CompiledProgram::new(&Program::parse("inp z\ninp x\nmul z 3\neql z x\nadd y w").unwrap());
+        let start = Registers { w: 5, x: 0, y: 1, z: 0 };
+
+        assert_eq!(Registers { w: 5, x: 9, y: 6, z: 1 }, program.run(&start, &[3, 9]).unwrap());
+        assert_eq!(Registers { w: 5, x: 7, y: 6, z: 0 }, program.run(&start, &[2, 7]).unwrap());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let program = Program::parse("inp w\nadd z w\nmul z 26\ninp w\nadd z w\nmod w 4\nadd y 2\ndiv z y").unwrap();
+        let mut alu = ALU::new(&[7, 6]);
+        alu.run(&program).unwrap();
+
+        // running the blocks one after another ends up in the same place
+        let blocks: Vec<_> = program.into_blocks().iter().map(CompiledProgram::new).collect();
+        let first = blocks[0].run(&Registers { w: 0, x: 0, y: 0, z: 0 }, &[7]).unwrap();
+        assert_eq!(alu.state(), blocks[1].run(&first, &[6]).unwrap());
+    }
+
+    #[test]
+    fn test_errors() {
+        let program = CompiledProgram::new(&Program::parse("inp w\nmod w x\ninp x\ndiv w x").unwrap());
+        let start = Registers { w: 0, x: 5, y: 0, z: 0 };
+
+        assert_eq!(Err(AluError { index: 3, kind: AluErrorKind::DivisionByZero }), program.run(&start, &[3, 0]));
+        assert_eq!(Err(AluError { index: 1, kind: AluErrorKind::InvalidModulo(-3, 5) }), program.run(&start, &[-3, 1]));
+        assert_eq!(Err(AluError { index: 2, kind: AluErrorKind::MissingInput }), program.run(&start, &[3]));
+
+        let overflow = Err(AluError { index: 1, kind: AluErrorKind::Overflow });
+        let big = Registers { w: Reg::MAX, x: -1, y: 2, z: Reg::MIN };
+        assert_eq!(overflow, CompiledProgram::new(&Program::parse("inp x\nadd w x").unwrap()).run(&big, &[1]));
+        assert_eq!(overflow, CompiledProgram::new(&Program::parse("inp x\nmul w y").unwrap()).run(&big, &[1]));
+        assert_eq!(overflow, CompiledProgram::new(&Program::parse("inp x\ndiv z -1").unwrap()).run(&big, &[1]));
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::str::FromStr;
+
+use common::ParseError;
+
+use crate::alu::{AluError, Op, Program, Reg, Registers, ALU};
+
+/// How a `Condition` compares a register to a value.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+}
+
+/// A test on one register, like `z == 0`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Condition {
+    pub var: char,
+    pub comparison: Comparison,
+    pub value: Reg,
+}
+
+impl Condition {
+    pub fn holds(&self, registers: &Registers) -> bool {
+        let reg = registers.get(self.var).unwrap();
+        match self.comparison {
+            Comparison::Eq => reg == self.value,
+            Comparison::Ne => reg != self.value,
+            Comparison::Lt => reg < self.value,
+            Comparison::Le => reg <= self.value,
+            Comparison::Gt => reg > self.value,
+            Comparison::Ge => reg >= self.value,
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim_start();
+        let var = match trimmed.chars().next() {
+            Some(var @ ('w' | 'x' | 'y' | 'z')) => var,
+            _ => return Err(ParseError::at(s, trimmed, "expected a register")),
+        };
+
+        let rest = trimmed[1..].trim_start();
+        // the two char symbols first, so that `<=` is not read as `<`
+        let comparison = [Comparison::Eq, Comparison::Ne, Comparison::Le, Comparison::Ge, Comparison::Lt, Comparison::Gt]
+            .into_iter()
+            .find(|comparison| rest.starts_with(comparison.symbol()))
+            .ok_or_else(|| ParseError::at(s, rest, "expected one of `==`, `!=`, `<`, `<=`, `>` or `>=`"))?;
+
+        let value = rest[comparison.symbol().len()..].trim();
+        let value = value.parse()
+            .map_err(|_| ParseError::at(s, value, format!("`{}` is not a number", value)))?;
+
+        Ok(Condition { var, comparison, value })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.var, self.comparison.symbol(), self.value)
+    }
+}
+
+/// Where a `Debugger` should stop.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Breakpoint {
+    /// Before running the instruction at this index, except the one a
+    /// `resume` starts from once the program has started.
+    Instruction(usize),
+    /// After an instruction makes the condition hold, when it did not before.
+    Condition(Condition),
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Instruction(index) => write!(f, "instruction {}", index),
+            Breakpoint::Condition(condition) => write!(f, "{}", condition),
+        }
+    }
+}
+
+/// One instruction run, and the registers around it.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct TraceEntry {
+    pub index: usize,
+    pub op: Op,
+    pub before: Registers,
+    pub after: Registers,
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>4}  {:<12}{}", self.index, self.op.to_string(), self.after)
+    }
+}
+
+/// Why `Debugger::resume` returned.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Stop {
+    /// Stopped at the breakpoint with this index in `Debugger::breakpoints`.
+    Breakpoint(usize),
+    /// The next instruction cannot be run.
+    Error(AluError),
+    Finished,
+}
+
+/// Runs a program an instruction at a time, keeping a trace of every
+/// instruction run.
+pub struct Debugger<'a> {
+    program: &'a Program,
+    alu: ALU,
+    // index of the next instruction to run
+    pc: usize,
+    breakpoints: Vec<Breakpoint>,
+    trace: Vec<TraceEntry>,
+    // whether it has stepped or stopped at a breakpoint yet
+    started: bool,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(program: &'a Program, input: &[Reg]) -> Self {
+        Self { program, alu: ALU::new(input), pc: 0, breakpoints: Vec::new(), trace: Vec::new(), started: false }
+    }
+
+    /// The index of the next instruction to run.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn registers(&self) -> Registers {
+        self.alu.state()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Every instruction run so far, in order.
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Add a breakpoint, returning its index.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        self.breakpoints.push(breakpoint);
+        self.breakpoints.len() - 1
+    }
+
+    pub fn remove_breakpoint(&mut self, index: usize) -> Option<Breakpoint> {
+        (index < self.breakpoints.len()).then(|| self.breakpoints.remove(index))
+    }
+
+    /// Run the next instruction, or return `None` if there are none left.
+    /// An instruction that fails is not run, and stays the next one.
+    pub fn step(&mut self) -> Result<Option<TraceEntry>, AluError> {
+        let op = match self.program.get(self.pc) {
+            Some(op) => *op,
+            None => return Ok(None),
+        };
+        self.started = true;
+        let before = self.alu.state();
+        self.alu.op(&op).map_err(|kind| AluError { index: self.pc, kind })?;
+
+        let entry = TraceEntry { index: self.pc, op, before, after: self.alu.state() };
+        self.trace.push(entry);
+        self.pc += 1;
+        Ok(Some(entry))
+    }
+
+    /// Run until a breakpoint, an error or the end of the program. Once the
+    /// program has started, the instruction it resumes from is run whatever
+    /// breakpoints it has, so that resuming from a breakpoint gets past it.
+    pub fn resume(&mut self) -> Stop {
+        let mut check = !self.started;
+        loop {
+            if check {
+                if let Some(hit) = self.breakpoints.iter().position(|breakpoint| *breakpoint == Breakpoint::Instruction(self.pc)) {
+                    self.started = true;
+                    return Stop::Breakpoint(hit);
+                }
+            }
+            check = true;
+
+            let entry = match self.step() {
+                Ok(Some(entry)) => entry,
+                Ok(None) => return Stop::Finished,
+                Err(error) => return Stop::Error(error),
+            };
+            if let Some(hit) = self.breakpoints.iter().position(|breakpoint| match breakpoint {
+                Breakpoint::Instruction(_) => false,
+                Breakpoint::Condition(condition) => !condition.holds(&entry.before) && condition.holds(&entry.after),
+            }) {
+                return Stop::Breakpoint(hit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alu::AluErrorKind;
+
+    fn program() -> Program {
+        // z = 3 * (first input) + (second input)
+        Program::parse("inp z\nmul z 3\ninp x\nadd z x\nmod z 5").unwrap()
+    }
+
+    #[test]
+    fn test_condition_parse() {
+        let condition: Condition = "z <= -2".parse().unwrap();
+
+        assert_eq!(Condition { var: 'z', comparison: Comparison::Le, value: -2 }, condition);
+        assert_eq!("z <= -2", condition.to_string());
+        assert_eq!(1, "q == 1".parse::<Condition>().unwrap_err().column());
+        assert_eq!(3, "x ~ 1".parse::<Condition>().unwrap_err().column());
+    }
+
+    #[test]
+    fn test_step() {
+        let program = program();
+        let mut debugger = Debugger::new(&program, &[4, 2]);
+
+        assert_eq!(4, debugger.step().unwrap().unwrap().after.z);
+        assert_eq!(12, debugger.step().unwrap().unwrap().after.z);
+        while debugger.step().unwrap().is_some() {}
+
+        assert!(debugger.is_finished());
+        assert_eq!(Registers { w: 0, x: 2, y: 0, z: 4 }, debugger.registers());
+        let zs: Vec<_> = debugger.trace().iter().map(|entry| entry.after.z).collect();
+        assert_eq!(vec![4, 12, 12, 14, 4], zs);
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let program = program();
+        let mut debugger = Debugger::new(&program, &[4, 2]);
+        debugger.add_breakpoint(Breakpoint::Instruction(3));
+        debugger.add_breakpoint(Breakpoint::Condition("z < 10".parse().unwrap()));
+
+        assert_eq!(Stop::Breakpoint(0), debugger.resume());
+        assert_eq!(3, debugger.pc());
+        // z was already under 10 before the first instruction, so it only
+        // stops once z drops back under
+        assert_eq!(Stop::Breakpoint(1), debugger.resume());
+        assert_eq!(5, debugger.pc());
+        assert_eq!(Stop::Finished, debugger.resume());
+    }
+
+    #[test]
+    fn test_break_zero() {
+        let program = program();
+        let mut debugger = Debugger::new(&program, &[4, 2]);
+        debugger.add_breakpoint(Breakpoint::Instruction(0));
+
+        // it stops before running anything, then gets past it
+        assert_eq!(Stop::Breakpoint(0), debugger.resume());
+        assert_eq!(0, debugger.pc());
+        assert!(debugger.trace().is_empty());
+        assert_eq!(Stop::Finished, debugger.resume());
+    }
+
+    #[test]
+    fn test_error() {
+        let program = program();
+        let mut debugger = Debugger::new(&program, &[4]);
+        let error = AluError { index: 2, kind: AluErrorKind::MissingInput };
+
+        assert_eq!(Stop::Error(error), debugger.resume());
+        // the failing instruction is still next
+        assert_eq!(2, debugger.pc());
+        assert_eq!(12, debugger.registers().z);
+        assert_eq!(Err(error), debugger.step());
+    }
+}
@@ -1,105 +1,57 @@
 mod alu;
-pub use alu::{Program, ALU};
+mod compile;
+mod debug;
+mod optimize;
+mod search;
+mod symbolic;
+#[cfg(test)]
+mod properties;
+pub use alu::{AluError, AluErrorKind, Arg, Op, Program, Reg, Registers, ALU};
+pub use compile::CompiledProgram;
+pub use debug::{Breakpoint, Comparison, Condition, Debugger, Stop, TraceEntry};
+pub use optimize::{eliminate_dead_writes, optimize, propagate_constants, remove_identities, Pass, PASSES};
+pub use search::{Goal, Search};
+pub use symbolic::{digit_constraints, max_input, min_input, Constraint, Expr, SymbolicError};
 
-use alu::{Registers, Reg};
-use std::collections::HashSet;
+use common::{Answer, Day, ParseError, Solution};
 
-pub fn monad_max(program: &Program) -> Option<String> {
-    let blocks = program.into_blocks();
-    let state = Registers { w:0, x:0, y:0, z:0 };
-    let mut memo = HashSet::new();
-    monad_max_block(&blocks, 0, state, &mut memo).map(|result| {
-        result.into_iter().rev()
-            .map(|digit| digit.to_string())
-            .collect()
-    })
-}
+pub const DAY: Day = Day::new::<Solver>(24, "Arithmetic Logic Unit", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
 
-fn monad_max_block(blocks: &[Program], block: usize, state: Registers, memo: &mut Memo) -> Option<Vec<Reg>> {
-    for input in (1..10).rev() {
-        if memo.contains(&MemoEntry{ block, input, z: state.z }) {
-            continue;
-        }
+impl Solution for Solver {
+    type Input = Program;
 
-        let mut alu = ALU::new(&[input]);
-        alu.set_state(&state);
-        alu.run(&blocks[block]);
-        let output = alu.state();
-        
-        // blocks left to analyze
-        if block + 1 < blocks.len() {
-            if let Some(mut digits) = monad_max_block(blocks, block + 1, output, memo) {
-                digits.push(input);
-                return Some(digits);
-            } else {
-                memo.insert(MemoEntry{ block, input, z: state.z });
-            }
-        // end reached
-        } else {
-            if output.z == 0 {
-                return Some(vec![input]);
-            } else {
-                memo.insert(MemoEntry{ block, input, z: state.z });
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Program::parse(input)
     }
 
-    None
-}
+    fn part_one(program: &Self::Input) -> Answer {
+        model_number(Search::new(Goal::Max).run(program))
+    }
 
-pub fn monad_min(program: &Program) -> Option<String> {
-    let blocks = program.into_blocks();
-    let state = Registers { w:0, x:0, y:0, z:0 };
-    let mut memo = HashSet::new();
-    monad_min_block(&blocks, 0, state, &mut memo).map(|result| {
-        result.into_iter().rev()
-            .map(|digit| digit.to_string())
-            .collect()
-    })
+    fn part_two(program: &Self::Input) -> Answer {
+        model_number(Search::new(Goal::Min).run(program))
+    }
 }
 
-fn monad_min_block(blocks: &[Program], block: usize, state: Registers, memo: &mut Memo) -> Option<Vec<Reg>> {
-    for input in 1..10 {
-        if memo.contains(&MemoEntry{ block, input, z: state.z }) {
-            continue;
-        }
-
-        let mut alu = ALU::new(&[input]);
-        alu.set_state(&state);
-        alu.run(&blocks[block]);
-        let output = alu.state();
-        
-        // blocks left to analyze
-        if block + 1 < blocks.len() {
-            if let Some(mut digits) = monad_min_block(blocks, block + 1, output, memo) {
-                digits.push(input);
-                return Some(digits);
-            } else {
-                memo.insert(MemoEntry{ block, input, z: state.z });
-            }
-        // end reached
-        } else {
-            if output.z == 0 {
-                return Some(vec![input]);
-            } else {
-                memo.insert(MemoEntry{ block, input, z: state.z });
-            }
-        }
+// a number where it fits, and the digits as they are where there are too many
+fn model_number(digits: Option<String>) -> Answer {
+    match digits {
+        Some(digits) => digits.parse::<u64>().map_or(Answer::Text(digits), Answer::from),
+        None => Answer::None,
     }
-
-    None
 }
 
-// Memo only tracks input and z register per block, as w is always set to input,
-// and x/y are always set to 0 before being used in every MONAD block.
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct MemoEntry {
-    block: usize,
-    input: Reg,
-    z: Reg,
+/// The largest model number MONAD accepts, searching on a single thread.
+pub fn monad_max(program: &Program) -> Option<String> {
+    Search { threads: 1, ..Search::new(Goal::Max) }.run(program)
 }
 
-type Memo = HashSet<MemoEntry>;
+/// The smallest model number MONAD accepts, searching on a single thread.
+pub fn monad_min(program: &Program) -> Option<String> {
+    Search { threads: 1, ..Search::new(Goal::Min) }.run(program)
+}
 
 #[cfg(test)]
 mod tests {
@@ -127,8 +79,15 @@ mod tests {
             "add y 8",
             "mul y x",
             "add z y",
-        ].join("\n"));
+        ].join("\n")).unwrap();
 
         assert_eq!(result, monad_max(&program).unwrap())
     }
+
+    #[test]
+    fn test_model_number() {
+        assert_eq!(Answer::Number(13579), model_number(Some("13579".to_string())));
+        assert_eq!(Answer::Text("9".repeat(20)), model_number(Some("9".repeat(20))));
+        assert_eq!(Answer::None, model_number(None));
+    }
 }
@@ -0,0 +1,260 @@
+use crate::alu::{add, divide, index, multiply, remainder, Arg, Op, Program, Reg};
+
+/// A pass rewrites a program's instructions into equivalent ones.
+pub type Pass = fn(&[Op]) -> Vec<Op>;
+
+/// The passes `optimize` runs, in order.
+pub const PASSES: [Pass; 3] = [propagate_constants, remove_identities, eliminate_dead_writes];
+
+/// Run every pass until none of them changes the program any more.
+///
+/// The program is taken to start with every register at 0, as `ALU::new`
+/// leaves them, and all four registers are kept as they would end up. Every
+/// `inp` is kept, so the program reads the same input, and so is anything
+/// that might fail.
+pub fn optimize(program: &Program) -> Program {
+    let mut ops: Vec<Op> = program.instructions().copied().collect();
+    loop {
+        let optimized = PASSES.iter().fold(ops.clone(), |ops, pass| pass(&ops));
+        if optimized == ops {
+            return Program::new(&ops);
+        }
+        ops = optimized;
+    }
+}
+
+/// Replace registers whose value is known with that value, and put off
+/// setting a register to a known value until something needs it, which
+/// drops the instructions that only worked it out.
+pub fn propagate_constants(ops: &[Op]) -> Vec<Op> {
+    // what each register holds in the program given, and in the one being
+    // written, where `None` is not known
+    let mut known: [Option<Reg>; 4] = [Some(0); 4];
+    let mut written: [Option<Reg>; 4] = [Some(0); 4];
+    let mut result = Vec::new();
+
+    for op in ops {
+        let (var, arg) = match operands(op) {
+            Some(operands) => operands,
+            None => {
+                // only `inp` has a single operand
+                known[index(op.var())] = None;
+                written[index(op.var())] = None;
+                result.push(*op);
+                continue;
+            },
+        };
+        let arg = match arg {
+            Arg::Var(other) => known[index(other)].map_or(arg, Arg::Num),
+            Arg::Num(_) => arg,
+        };
+        let op = with_operands(op, var, arg);
+
+        let after = match (known[index(var)], arg) {
+            (Some(a), Arg::Num(b)) => fold(&op, a, b),
+            // anything times 0 is 0, even when it is not known
+            (Some(0), _) | (_, Arg::Num(0)) if matches!(op, Op::Mul(..)) => Some(0),
+            _ => None,
+        };
+        if after.is_none() {
+            set(&mut result, var, known[index(var)], &mut written[index(var)]);
+            result.push(op);
+            written[index(var)] = None;
+        }
+        known[index(var)] = after;
+    }
+
+    for var in ['w', 'x', 'y', 'z'] {
+        set(&mut result, var, known[index(var)], &mut written[index(var)]);
+    }
+    result
+}
+
+// bring register `var` up to the known value, if it has one
+fn set(result: &mut Vec<Op>, var: char, known: Option<Reg>, written: &mut Option<Reg>) {
+    let value = match known {
+        Some(value) if Some(value) != *written => value,
+        _ => return,
+    };
+
+    match written.and_then(|written| value.checked_sub(written)) {
+        Some(difference) => result.push(Op::Add(Arg::Var(var), Arg::Num(difference))),
+        None => {
+            result.push(Op::Mul(Arg::Var(var), Arg::Num(0)));
+            if value != 0 {
+                result.push(Op::Add(Arg::Var(var), Arg::Num(value)));
+            }
+        },
+    }
+    *written = known;
+}
+
+/// Drop instructions that leave their register as it was: adding 0, and
+/// multiplying or dividing by 1.
+pub fn remove_identities(ops: &[Op]) -> Vec<Op> {
+    ops.iter()
+        .filter(|op| !matches!(op, Op::Add(_, Arg::Num(0)) | Op::Mul(_, Arg::Num(1)) | Op::Div(_, Arg::Num(1))))
+        .copied()
+        .collect()
+}
+
+/// Drop instructions whose result is overwritten before anything reads it,
+/// unless they might fail with the registers starting at 0.
+pub fn eliminate_dead_writes(ops: &[Op]) -> Vec<Op> {
+    let ranges = ranges(ops);
+    // the registers that are read before being written, working backwards
+    // from the end, where they are all wanted
+    let mut live = [true; 4];
+    let mut result = Vec::new();
+
+    for (op, ranges) in ops.iter().zip(ranges).rev() {
+        let (var, arg) = match operands(op) {
+            Some(operands) => operands,
+            None => {
+                live[index(op.var())] = false;
+                result.push(*op);
+                continue;
+            },
+        };
+        if !live[index(var)] && !may_fail(op, ranges[index(var)], range(&ranges, arg)) {
+            continue;
+        }
+
+        // multiplying by 0 does not need the old value
+        live[index(var)] = !matches!(op, Op::Mul(_, Arg::Num(0)));
+        if let Arg::Var(other) = arg {
+            live[index(other)] = true;
+        }
+        result.push(*op);
+    }
+
+    result.reverse();
+    result
+}
+
+// the register and argument of anything but `inp`
+fn operands(op: &Op) -> Option<(char, Arg)> {
+    match op {
+        Op::Inp(_) => None,
+        Op::Add(_, arg) | Op::Mul(_, arg) | Op::Div(_, arg) | Op::Mod(_, arg) | Op::Eql(_, arg) => Some((op.var(), *arg)),
+    }
+}
+
+fn with_operands(op: &Op, var: char, arg: Arg) -> Op {
+    let var = Arg::Var(var);
+    match op {
+        Op::Inp(_) => Op::Inp(var),
+        Op::Add(..) => Op::Add(var, arg),
+        Op::Mul(..) => Op::Mul(var, arg),
+        Op::Div(..) => Op::Div(var, arg),
+        Op::Mod(..) => Op::Mod(var, arg),
+        Op::Eql(..) => Op::Eql(var, arg),
+    }
+}
+
+// the result of `op` on `a` and `b`, or `None` if the ALU would fail
+fn fold(op: &Op, a: Reg, b: Reg) -> Option<Reg> {
+    match op {
+        Op::Inp(_) => None,
+        Op::Add(..) => add(a, b).ok(),
+        Op::Mul(..) => multiply(a, b).ok(),
+        Op::Div(..) => divide(a, b).ok(),
+        Op::Mod(..) => remainder(a, b).ok(),
+        Op::Eql(..) => Some(if a == b { 1 } else { 0 }),
+    }
+}
+
+// the smallest and largest value each register can hold before each
+// instruction, from registers starting at 0
+fn ranges(ops: &[Op]) -> Vec<[(Reg, Reg); 4]> {
+    let mut ranges = vec![[(0, 0); 4]];
+    for op in ops {
+        let mut after = *ranges.last().unwrap();
+        after[index(op.var())] = match operands(op) {
+            Some((var, arg)) => result_range(op, after[index(var)], range(&after, arg)),
+            None => (Reg::MIN, Reg::MAX),
+        };
+        ranges.push(after);
+    }
+    ranges
+}
+
+fn range(ranges: &[(Reg, Reg); 4], arg: Arg) -> (Reg, Reg) {
+    match arg {
+        Arg::Var(var) => ranges[index(var)],
+        Arg::Num(num) => (num, num),
+    }
+}
+
+// the range of what `op` leaves its register with, when it does not fail
+fn result_range(op: &Op, (a_lo, a_hi): (Reg, Reg), (b_lo, b_hi): (Reg, Reg)) -> (Reg, Reg) {
+    let bounds = |values: [Reg; 4]| (*values.iter().min().unwrap(), *values.iter().max().unwrap());
+    match op {
+        Op::Inp(_) => (Reg::MIN, Reg::MAX),
+        Op::Add(..) => (a_lo.saturating_add(b_lo), a_hi.saturating_add(b_hi)),
+        Op::Mul(..) => bounds([a_lo.saturating_mul(b_lo), a_lo.saturating_mul(b_hi), a_hi.saturating_mul(b_lo), a_hi.saturating_mul(b_hi)]),
+        // the divisor's sign is known, so the quotient is largest at the ends
+        Op::Div(..) if b_lo > 0 || b_hi < 0 => bounds([a_lo.saturating_div(b_lo), a_lo.saturating_div(b_hi), a_hi.saturating_div(b_lo), a_hi.saturating_div(b_hi)]),
+        Op::Div(..) => (Reg::MIN, Reg::MAX),
+        Op::Mod(..) => (0, a_hi.min(b_hi.saturating_sub(1)).max(0)),
+        Op::Eql(..) => (0, 1),
+    }
+}
+
+// a `mod` fails on negative numbers, and anything but `eql` can overflow
+fn may_fail(op: &Op, (a_lo, a_hi): (Reg, Reg), (b_lo, b_hi): (Reg, Reg)) -> bool {
+    match op {
+        Op::Inp(_) | Op::Eql(..) => false,
+        Op::Add(..) => a_lo.checked_add(b_lo).is_none() || a_hi.checked_add(b_hi).is_none(),
+        Op::Mul(..) => [(a_lo, b_lo), (a_lo, b_hi), (a_hi, b_lo), (a_hi, b_hi)].iter().any(|(a, b)| a.checked_mul(*b).is_none()),
+        // only `MIN / -1` overflows
+        Op::Div(..) => (b_lo <= 0 && 0 <= b_hi) || (a_lo == Reg::MIN && b_lo <= -1 && -1 <= b_hi),
+        Op::Mod(..) => a_lo < 0 || b_lo <= 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ops(code: &str) -> Vec<Op> {
+        Program::parse(code).unwrap().instructions().copied().collect()
+    }
+
+    fn code(ops: &[Op]) -> String {
+        Program::new(ops).to_string()
+    }
+
+    #[test]
+    fn test_propagate_constants() {
+        let optimized = propagate_constants(&ops("add x 3\nmul x 4\ninp w\nadd y x\neql z y\nmul w z\ndiv z y"));
+
+        // z starts at 0, so `mul w z` always leaves w at 0
+        assert_eq!("inp w\nmul w 0\nadd x 12\nadd y 12", code(&optimized));
+    }
+
+    #[test]
+    fn test_remove_identities() {
+        let optimized = remove_identities(&ops("inp z\ndiv z 1\nadd z 0\nmul z 1\nmul z 0\ndiv z 26"));
+
+        assert_eq!("inp z\nmul z 0\ndiv z 26", code(&optimized));
+    }
+
+    #[test]
+    fn test_eliminate_dead_writes() {
+        let optimized = eliminate_dead_writes(&ops("inp w\nadd x w\neql x 3\nmul x 0\nadd y w\ndiv y x\nmul y 0\nadd z 4\ninp z"));
+
+        // y is overwritten, but the division stays as it might fail
+        assert_eq!("inp w\nmul x 0\nadd y w\ndiv y x\nmul y 0\ninp z", code(&optimized));
+    }
+
+    #[test]
+    fn test_monad_block() {
+        let block = "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z 1\nadd x 12\neql x w\neql x 0\n\
+            mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y 4\nmul y x\nadd z y";
+        let optimized = optimize(&Program::parse(block).unwrap());
+
+        // z starts at 0, so the block only needs to push the digit
+        assert_eq!("inp w\nadd x 12\neql x w\neql x 0\nadd y w\nadd y 4\nmul y x\nadd z y", optimized.to_string());
+    }
+}
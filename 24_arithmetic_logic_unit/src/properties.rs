@@ -0,0 +1,71 @@
+use proptest::prelude::*;
+
+use crate::{optimize, AluError, Arg, CompiledProgram, Op, Program, Reg, Registers, ALU};
+
+fn var() -> impl Strategy<Value = Arg> {
+    prop::sample::select(vec!['w', 'x', 'y', 'z']).prop_map(Arg::Var)
+}
+
+fn arg() -> impl Strategy<Value = Arg> {
+    prop_oneof![var(), (-3..=30 as Reg).prop_map(Arg::Num)]
+}
+
+/// Random instructions, which may well fail.
+fn op() -> impl Strategy<Value = Op> {
+    (0..6, var(), arg()).prop_map(|(kind, var, arg)| match kind {
+        0 => Op::Inp(var),
+        1 => Op::Add(var, arg),
+        2 => Op::Mul(var, arg),
+        3 => Op::Div(var, arg),
+        4 => Op::Mod(var, arg),
+        _ => Op::Eql(var, arg),
+    })
+}
+
+fn program() -> impl Strategy<Value = Program> {
+    prop::collection::vec(op(), 0..40).prop_map(|ops| Program::new(&ops))
+}
+
+// enough digits for any `program()`
+fn input() -> impl Strategy<Value = Vec<Reg>> {
+    prop::collection::vec(1..=9 as Reg, 40)
+}
+
+fn registers() -> impl Strategy<Value = Registers> {
+    [-50..50 as Reg, -50..50, -50..50, -50..50].prop_map(|[w, x, y, z]| Registers { w, x, y, z })
+}
+
+fn run(program: &Program, start: &Registers, input: &[Reg]) -> Result<Registers, AluError> {
+    let mut alu = ALU::new(input);
+    alu.set_state(start);
+    alu.run(program)
+}
+
+const ZERO: Registers = Registers { w: 0, x: 0, y: 0, z: 0 };
+
+proptest! {
+    #[test]
+    fn optimized_programs_agree(program in program(), input in input()) {
+        // the optimized program fails whenever the original does, though
+        // maybe at another instruction
+        let optimized = optimize(&program);
+        prop_assert!(optimized.len() <= program.len());
+        prop_assert_eq!(run(&program, &ZERO, &input).ok(), run(&optimized, &ZERO, &input).ok(), "optimized to\n{}", optimized);
+    }
+
+    #[test]
+    fn optimized_programs_print_back(program in program()) {
+        let optimized = optimize(&program);
+        let reparsed = Program::parse(&optimized.to_string()).unwrap();
+
+        prop_assert_eq!(optimized.to_string(), reparsed.to_string());
+    }
+
+    #[test]
+    fn compiled_programs_agree(program in program(), start in registers(), input in input()) {
+        let compiled = CompiledProgram::new(&program);
+
+        // including where and how programs fail
+        prop_assert_eq!(run(&program, &start, &input), compiled.run(&start, &input));
+    }
+}
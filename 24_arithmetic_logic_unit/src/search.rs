@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::alu::{Program, Reg, Registers};
+use crate::compile::CompiledProgram;
+
+/// Which model number a `Search` looks for.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Goal {
+    Max,
+    Min,
+}
+
+impl Goal {
+    // the digits in the order to try them
+    fn digits(self) -> [Reg; 9] {
+        match self {
+            Goal::Max => [9, 8, 7, 6, 5, 4, 3, 2, 1],
+            Goal::Min => [1, 2, 3, 4, 5, 6, 7, 8, 9],
+        }
+    }
+}
+
+/// A search for the largest or smallest model number MONAD accepts, one
+/// digit per block, across threads.
+///
+/// The first few digits are tried up front, and every prefix that is left
+/// becomes a task. Threads take the tasks in order as they finish others,
+/// and the answer is that of the first task to find one, however the tasks
+/// happen to be shared out.
+#[derive(Debug, Copy, Clone)]
+pub struct Search {
+    pub goal: Goal,
+    pub threads: usize,
+    /// Print each task's outcome to stderr.
+    pub verbose: bool,
+}
+
+impl Search {
+    pub fn new(goal: Goal) -> Self {
+        Self { goal, threads: thread::available_parallelism().map_or(1, |n| n.get()), verbose: false }
+    }
+
+    pub fn run(&self, program: &Program) -> Option<String> {
+        let blocks: Vec<_> = program.into_blocks().iter().map(CompiledProgram::new).collect();
+        let threads = self.threads.max(1);
+        let tasks = self.tasks(&blocks, threads * 8);
+        let memo = Memo::new();
+        // the first task to find a model number, which later ones give way to
+        let best = AtomicUsize::new(usize::MAX);
+        let next = AtomicUsize::new(0);
+
+        let found = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|_| scope.spawn(|| {
+                    let mut found = Vec::new();
+                    loop {
+                        let task = next.fetch_add(1, Ordering::Relaxed);
+                        if task >= tasks.len() || task > best.load(Ordering::Relaxed) {
+                            return found;
+                        }
+
+                        let (ref prefix, state) = tasks[task];
+                        let mut digits = prefix.clone();
+                        let outcome = Worker { goal: self.goal, blocks: &blocks, memo: &memo, best: &best, task }
+                            .search(prefix.len(), state, &mut digits);
+                        if self.verbose {
+                            eprintln!("{}: {}", to_string(prefix), match outcome {
+                                Outcome::Found => to_string(&digits),
+                                Outcome::Dead => "none".to_string(),
+                                Outcome::Stopped => "stopped".to_string(),
+                            });
+                        }
+                        if outcome == Outcome::Found {
+                            best.fetch_min(task, Ordering::Relaxed);
+                            found.push((task, digits));
+                        }
+                    }
+                }))
+                .collect();
+
+            handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        found.into_iter().min_by_key(|(task, _)| *task).map(|(_, digits)| to_string(&digits))
+    }
+
+    // the prefixes left after trying digits a block at a time until there
+    // are at least `count`, in the order the goal prefers them
+    fn tasks(&self, blocks: &[CompiledProgram], count: usize) -> Vec<(Vec<Reg>, Registers)> {
+        let mut tasks = vec![(Vec::new(), Registers { w: 0, x: 0, y: 0, z: 0 })];
+
+        for block in blocks {
+            if tasks.len() >= count {
+                break;
+            }
+            tasks = tasks.into_iter()
+                .flat_map(|(prefix, state)| self.goal.digits().into_iter().filter_map(move |input| {
+                    let output = block.run(&state, &[input]).ok()?;
+                    let mut prefix = prefix.clone();
+                    prefix.push(input);
+                    Some((prefix, output))
+                }))
+                .collect();
+        }
+
+        tasks
+    }
+}
+
+fn to_string(digits: &[Reg]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect()
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Outcome {
+    Found,
+    // nothing from here is accepted
+    Dead,
+    // an earlier task found a model number first
+    Stopped,
+}
+
+struct Worker<'a> {
+    goal: Goal,
+    blocks: &'a [CompiledProgram],
+    memo: &'a Memo,
+    best: &'a AtomicUsize,
+    task: usize,
+}
+
+impl Worker<'_> {
+    fn search(&self, block: usize, state: Registers, digits: &mut Vec<Reg>) -> Outcome {
+        if block == self.blocks.len() {
+            return if state.z == 0 { Outcome::Found } else { Outcome::Dead };
+        }
+        if self.best.load(Ordering::Relaxed) < self.task {
+            return Outcome::Stopped;
+        }
+        let entry = MemoEntry { block, z: state.z };
+        if self.memo.contains(&entry) {
+            return Outcome::Dead;
+        }
+
+        for input in self.goal.digits() {
+            // the ALU cannot get past this digit
+            let output = match self.blocks[block].run(&state, &[input]) {
+                Ok(output) => output,
+                Err(_) => continue,
+            };
+
+            digits.push(input);
+            match self.search(block + 1, output, digits) {
+                Outcome::Dead => {
+                    digits.pop();
+                },
+                outcome => return outcome,
+            }
+        }
+
+        self.memo.insert(entry);
+        Outcome::Dead
+    }
+}
+
+// Memo only tracks the z register per block, as w is always set to input,
+// and x/y are always set to 0 before being used in every MONAD block.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct MemoEntry {
+    block: usize,
+    z: Reg,
+}
+
+const SHARDS: usize = 64;
+
+// the dead ends found so far, split into shards so that threads seldom wait
+// on each other
+struct Memo {
+    shards: Vec<Mutex<HashSet<MemoEntry>>>,
+}
+
+impl Memo {
+    fn new() -> Self {
+        Self { shards: (0..SHARDS).map(|_| Mutex::new(HashSet::new())).collect() }
+    }
+
+    fn shard(&self, entry: &MemoEntry) -> &Mutex<HashSet<MemoEntry>> {
+        // a cheap mix of the fields, as the sets hash them properly anyway
+        let mix = (entry.z as u64 ^ (entry.block as u64) << 48).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        &self.shards[(mix >> 58) as usize % SHARDS]
+    }
+
+    fn contains(&self, entry: &MemoEntry) -> bool {
+        self.shard(entry).lock().unwrap().contains(entry)
+    }
+
+    fn insert(&self, entry: MemoEntry) {
+        self.shard(&entry).lock().unwrap().insert(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a MONAD where each pair of digits `(i, j)` must have `d[j] = d[i] + offset`
+    fn monad(pairs: &[(usize, usize, Reg)]) -> Program {
+        let mut blocks = vec![String::new(); pairs.len() * 2];
+        for &(i, j, offset) in pairs {
+            blocks[i] = block(false, 0, 0);
+            blocks[j] = block(true, offset, 0);
+        }
+        Program::parse(&blocks.join("\n")).unwrap()
+    }
+
+    fn block(pop: bool, check: Reg, push: Reg) -> String {
+        [
+            "inp w", "mul x 0", "add x z", "mod x 26",
+            if pop { "div z 26" } else { "div z 1" },
+            &format!("add x {}", if pop { check } else { 10 }),
+            "eql x w", "eql x 0", "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y",
+            "mul y 0", "add y w", &format!("add y {}", push), "mul y x", "add z y",
+        ].join("\n")
+    }
+
+    #[test]
+    fn test_search() {
+        let program = monad(&[(0, 3, 2), (1, 2, -4)]);
+
+        for threads in 1..=4 {
+            let search = |goal| Search { threads, ..Search::new(goal) }.run(&program);
+            assert_eq!(Some("7959".to_string()), search(Goal::Max));
+            assert_eq!(Some("1513".to_string()), search(Goal::Min));
+        }
+    }
+
+    #[test]
+    fn test_no_model_number() {
+        // the second digit would have to be the first plus 9
+        let program = monad(&[(0, 1, 9)]);
+
+        assert_eq!(None, Search::new(Goal::Max).run(&program));
+    }
+
+    #[test]
+    fn test_invalid_digits() {
+        // every first digit but 9 makes a `mod` of a negative number
+        let program = Program::parse("inp w\nadd x w\nadd x -9\nmod x 2\ninp z\nadd z -1").unwrap();
+
+        assert_eq!(Some("91".to_string()), Search::new(Goal::Min).run(&program));
+    }
+}
@@ -0,0 +1,454 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::alu::{Arg, Op, Program, Reg};
+
+/// A register's value as an expression of the input digits, which are each
+/// taken to be between 1 and 9.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Expr {
+    Num(Reg),
+    // the input read by the nth `inp`, counting from 0
+    Digit(usize),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Mod(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// The smallest and largest values the expression can take.
+    pub fn range(&self) -> (Reg, Reg) {
+        match self {
+            Expr::Num(num) => (*num, *num),
+            Expr::Digit(_) => (1, 9),
+            Expr::Add(a, b) => {
+                let ((a_lo, a_hi), (b_lo, b_hi)) = (a.range(), b.range());
+                (a_lo.saturating_add(b_lo), a_hi.saturating_add(b_hi))
+            },
+            Expr::Mul(a, b) => {
+                let ((a_lo, a_hi), (b_lo, b_hi)) = (a.range(), b.range());
+                let products = [a_lo.saturating_mul(b_lo), a_lo.saturating_mul(b_hi), a_hi.saturating_mul(b_lo), a_hi.saturating_mul(b_hi)];
+                (*products.iter().min().unwrap(), *products.iter().max().unwrap())
+            },
+            Expr::Div(a, b) => match (a.range(), b.range()) {
+                ((a_lo, a_hi), (b_lo, b_hi)) if b_lo > 0 => {
+                    let quotients = [a_lo / b_lo, a_lo / b_hi, a_hi / b_lo, a_hi / b_hi];
+                    (*quotients.iter().min().unwrap(), *quotients.iter().max().unwrap())
+                },
+                _ => (Reg::MIN, Reg::MAX),
+            },
+            Expr::Mod(a, b) => match (a.range(), b.range()) {
+                ((a_lo, a_hi), (b_lo, _)) if a_lo >= 0 && a_hi < b_lo => (0, a_hi),
+                ((a_lo, _), (_, b_hi)) if a_lo >= 0 && b_hi > 0 => (0, b_hi - 1),
+                _ => (Reg::MIN, Reg::MAX),
+            },
+        }
+    }
+
+    fn is_within(&self, lo: Reg, hi: Reg) -> bool {
+        let (self_lo, self_hi) = self.range();
+        lo <= self_lo && self_hi <= hi
+    }
+
+    // `None` when folding the constants overflows
+    fn add(a: Expr, b: Expr) -> Option<Expr> {
+        Some(match (a, b) {
+            (Expr::Num(a), Expr::Num(b)) => Expr::Num(a.checked_add(b)?),
+            (Expr::Num(0), e) | (e, Expr::Num(0)) => e,
+            // constants go on the right, and gather there
+            (Expr::Num(num), e) | (e, Expr::Num(num)) => match e {
+                Expr::Add(e, inner) if matches!(*inner, Expr::Num(_)) => {
+                    let Expr::Num(inner) = *inner else { unreachable!() };
+                    Expr::add(*e, Expr::Num(inner.checked_add(num)?))?
+                },
+                e => Expr::Add(Box::new(e), Box::new(Expr::Num(num))),
+            },
+            (a, b) => Expr::Add(Box::new(a), Box::new(b)),
+        })
+    }
+
+    // `None` when folding the constants overflows
+    fn mul(a: Expr, b: Expr) -> Option<Expr> {
+        Some(match (a, b) {
+            (Expr::Num(a), Expr::Num(b)) => Expr::Num(a.checked_mul(b)?),
+            (Expr::Num(0), _) | (_, Expr::Num(0)) => Expr::Num(0),
+            (Expr::Num(1), e) | (e, Expr::Num(1)) => e,
+            (Expr::Num(num), e) | (e, Expr::Num(num)) => Expr::Mul(Box::new(e), Box::new(Expr::Num(num))),
+            (a, b) => Expr::Mul(Box::new(a), Box::new(b)),
+        })
+    }
+
+    // `None` when folding the constants overflows, as `MIN / -1` does
+    fn div(a: Expr, b: Expr) -> Option<Expr> {
+        Some(match (a, b) {
+            (Expr::Num(a), Expr::Num(b)) if b != 0 => Expr::Num(a.checked_div(b)?),
+            (e, Expr::Num(1)) => e,
+            (e, Expr::Num(num)) if num > 0 && e.is_within(0, num - 1) => Expr::Num(0),
+            // (q * n + r) / n is q, when r is a digit of q in base n
+            (e, Expr::Num(num)) if num > 0 => match split_base(e, num) {
+                Ok((high, _)) => high,
+                Err(e) => Expr::Div(Box::new(e), Box::new(Expr::Num(num))),
+            },
+            (a, b) => Expr::Div(Box::new(a), Box::new(b)),
+        })
+    }
+
+    fn modulo(a: Expr, b: Expr) -> Expr {
+        match (a, b) {
+            (Expr::Num(a), Expr::Num(b)) if b > 0 => Expr::Num(a % b),
+            (e, Expr::Num(num)) if num > 0 && e.is_within(0, num - 1) => e,
+            // (q * n + r) % n is r, when r is a digit of q in base n
+            (e, Expr::Num(num)) if num > 0 => match split_base(e, num) {
+                Ok((_, low)) => low,
+                Err(e) => Expr::Mod(Box::new(e), Box::new(Expr::Num(num))),
+            },
+            (a, b) => Expr::Mod(Box::new(a), Box::new(b)),
+        }
+    }
+
+    // `None` when it cannot be told whether the two are equal
+    fn eql(a: &Expr, b: &Expr) -> Option<Expr> {
+        let ((a_lo, a_hi), (b_lo, b_hi)) = (a.range(), b.range());
+        if a == b {
+            Some(Expr::Num(1))
+        } else if a_hi < b_lo || b_hi < a_lo {
+            Some(Expr::Num(0))
+        } else {
+            None
+        }
+    }
+}
+
+// split `e` into `q` and `r` where `e` is `q * base + r`, `q` is not negative
+// and `r` is between 0 and `base - 1`, or give `e` back if it is not of
+// that form
+fn split_base(e: Expr, base: Reg) -> Result<(Expr, Expr), Expr> {
+    match e {
+        Expr::Mul(q, n) if *n == Expr::Num(base) && q.is_within(0, Reg::MAX) => Ok((*q, Expr::Num(0))),
+        Expr::Add(high, low) if low.is_within(0, base - 1) => match *high {
+            Expr::Mul(q, n) if *n == Expr::Num(base) && q.is_within(0, Reg::MAX) => Ok((*q, *low)),
+            high => Err(Expr::Add(Box::new(high), low)),
+        },
+        e => Err(e),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Num(num) => write!(f, "{}", num),
+            Expr::Digit(i) => write!(f, "d{}", i),
+            Expr::Add(a, b) => write!(f, "({} + {})", a, b),
+            Expr::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Expr::Div(a, b) => write!(f, "({} / {})", a, b),
+            Expr::Mod(a, b) => write!(f, "({} % {})", a, b),
+        }
+    }
+}
+
+/// Two digits of a valid input that must differ by a constant:
+/// `d<left> = d<right> + offset`, with digits counted from 0.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Constraint {
+    pub left: usize,
+    pub right: usize,
+    pub offset: Reg,
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "d{} = d{}", self.left, self.right)?;
+        match self.offset {
+            0 => Ok(()),
+            offset if offset > 0 => write!(f, " + {}", offset),
+            offset => write!(f, " - {}", -offset),
+        }
+    }
+}
+
+/// Why the digit constraints of a program could not be found.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SymbolicError {
+    /// Something the engine cannot reason about, like dividing by a value
+    /// that may be 0, or an equality that is not between two digits.
+    Unsupported(String),
+    /// The instruction at this index, counting from 0, gives a value too
+    /// large for a register.
+    Overflow(usize),
+    /// No input leaves z at 0.
+    NoSolution,
+}
+
+impl fmt::Display for SymbolicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SymbolicError::Unsupported(what) => write!(f, "cannot reason about {}", what),
+            SymbolicError::Overflow(index) => write!(f, "instruction {} overflows", index),
+            SymbolicError::NoSolution => write!(f, "no input leaves z at 0"),
+        }
+    }
+}
+
+impl Error for SymbolicError {}
+
+// one way through the program, deciding every undecided `eql` as it goes
+#[derive(Clone)]
+struct Path {
+    pc: usize,
+    digits: usize,
+    registers: [Expr; 4],
+    // each decided `eql`, and whether it was decided equal
+    assumptions: Vec<(Expr, Expr, bool)>,
+}
+
+impl Path {
+    fn reg(&mut self, arg: &Arg) -> &mut Expr {
+        match arg {
+            Arg::Var(var) => &mut self.registers["wxyz".find(*var).unwrap()],
+            Arg::Num(num) => panic!("'{}' is not variable", num),
+        }
+    }
+
+    fn val(&self, arg: &Arg) -> Expr {
+        match arg {
+            Arg::Var(var) => self.registers["wxyz".find(*var).unwrap()].clone(),
+            Arg::Num(num) => Expr::Num(*num),
+        }
+    }
+}
+
+/// Run `program` on symbolic digits to find how the digits of an input that
+/// leaves z at 0 must relate, like `d3 = d4 + 2`.
+///
+/// Every `eql` that depends on the input splits execution in two; this
+/// returns the constraints of the first way through, trying equality first,
+/// whose z is 0 and whose constraints are all between pairs of digits. That
+/// is the only such way through MONAD-style programs.
+pub fn digit_constraints(program: &Program) -> Result<Vec<Constraint>, SymbolicError> {
+    let start = Path { pc: 0, digits: 0, registers: [Expr::Num(0), Expr::Num(0), Expr::Num(0), Expr::Num(0)], assumptions: Vec::new() };
+    let mut paths = vec![start];
+    let mut unsupported = None;
+
+    while let Some(mut path) = paths.pop() {
+        while let Some(op) = program.get(path.pc) {
+            let overflow = SymbolicError::Overflow(path.pc);
+            path.pc += 1;
+            match op {
+                Op::Inp(var) => {
+                    *path.reg(var) = Expr::Digit(path.digits);
+                    path.digits += 1;
+                },
+                Op::Add(var, arg) => *path.reg(var) = Expr::add(path.val(var), path.val(arg)).ok_or(overflow)?,
+                Op::Mul(var, arg) => *path.reg(var) = Expr::mul(path.val(var), path.val(arg)).ok_or(overflow)?,
+                Op::Div(var, arg) | Op::Mod(var, arg) => {
+                    let divisor = path.val(arg);
+                    let (lo, hi) = divisor.range();
+                    // the ALU cannot divide by 0, or take the remainder of a
+                    // negative number or by one that is not positive
+                    let may_fail = if let Op::Div(..) = op { lo <= 0 && 0 <= hi } else { lo <= 0 || path.val(var).range().0 < 0 };
+                    if may_fail {
+                        return Err(SymbolicError::Unsupported(format!("`{}` with {} in {}..={}", op, arg, lo, hi)));
+                    }
+                    *path.reg(var) = if let Op::Div(..) = op {
+                        Expr::div(path.val(var), divisor).ok_or(overflow)?
+                    } else {
+                        Expr::modulo(path.val(var), divisor)
+                    };
+                },
+                Op::Eql(var, arg) => {
+                    let (a, b) = (path.val(var), path.val(arg));
+                    match Expr::eql(&a, &b) {
+                        Some(result) => *path.reg(var) = result,
+                        None => {
+                            // take the equal branch first, so push the other
+                            let mut unequal = path.clone();
+                            *unequal.reg(var) = Expr::Num(0);
+                            unequal.assumptions.push((a.clone(), b.clone(), false));
+                            paths.push(unequal);
+
+                            *path.reg(var) = Expr::Num(1);
+                            path.assumptions.push((a, b, true));
+                        },
+                    }
+                },
+            }
+        }
+
+        match &path.registers[3] {
+            Expr::Num(0) => match constraints(&path.assumptions) {
+                Ok(constraints) => return Ok(constraints),
+                Err(e) => unsupported = unsupported.or(Some(e)),
+            },
+            z if z.is_within(1, Reg::MAX) || z.is_within(Reg::MIN, -1) => {},
+            z => unsupported = unsupported.or(Some(SymbolicError::Unsupported(format!("whether z = {} is 0", z)))),
+        }
+    }
+
+    Err(unsupported.unwrap_or(SymbolicError::NoSolution))
+}
+
+fn constraints(assumptions: &[(Expr, Expr, bool)]) -> Result<Vec<Constraint>, SymbolicError> {
+    assumptions.iter()
+        .map(|(a, b, equal)| {
+            let unsupported = || SymbolicError::Unsupported(format!("{} {} {}", a, if *equal { "==" } else { "!=" }, b));
+            let ((i, a_offset), (j, b_offset)) = digit_plus(a).zip(digit_plus(b)).ok_or_else(unsupported)?;
+            if !equal || i == j {
+                return Err(unsupported());
+            }
+            // the earlier digit on the left
+            let (left, right, offset) = if i < j { (i, j, b_offset.checked_sub(a_offset)) } else { (j, i, a_offset.checked_sub(b_offset)) };
+            let offset = offset.ok_or_else(unsupported)?;
+            Ok(Constraint { left, right, offset })
+        })
+        .collect()
+}
+
+// `e` as a digit plus a constant
+fn digit_plus(e: &Expr) -> Option<(usize, Reg)> {
+    match e {
+        Expr::Digit(i) => Some((*i, 0)),
+        Expr::Add(a, b) => match (a.as_ref(), b.as_ref()) {
+            (Expr::Digit(i), Expr::Num(num)) => Some((*i, *num)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The largest input of `digits` digits from 1 to 9 meeting every
+/// constraint. Each digit may be in at most one constraint, as
+/// `digit_constraints` gives them, and chains of constraints are
+/// `Unsupported`.
+pub fn max_input(constraints: &[Constraint], digits: usize) -> Result<Vec<Reg>, SymbolicError> {
+    solve(constraints, digits, 9)
+}
+
+/// The smallest input of `digits` digits from 1 to 9 meeting every
+/// constraint, with the same limits as `max_input`.
+pub fn min_input(constraints: &[Constraint], digits: usize) -> Result<Vec<Reg>, SymbolicError> {
+    solve(constraints, digits, 1)
+}
+
+// each digit as close to `best` as its constraint allows
+fn solve(constraints: &[Constraint], digits: usize, best: Reg) -> Result<Vec<Reg>, SymbolicError> {
+    let mut input = vec![best; digits];
+    let mut constrained = vec![false; digits];
+
+    for &constraint in constraints {
+        let Constraint { left, right, offset } = constraint;
+        if left >= digits || right >= digits {
+            return Err(SymbolicError::Unsupported(format!("{} in an input of {} digits", constraint, digits)));
+        }
+        if constrained[left] || constrained[right] {
+            return Err(SymbolicError::Unsupported(format!("{} along with another constraint on its digits", constraint)));
+        }
+        // move whichever digit is further from `best` off it
+        let right_value = if (best == 9) == (offset >= 0) { best - offset } else { best };
+        input[right] = right_value;
+        // too far apart for any pair of digits
+        input[left] = right_value.checked_add(offset).ok_or(SymbolicError::NoSolution)?;
+        constrained[left] = true;
+        constrained[right] = true;
+    }
+
+    if input.iter().all(|digit| (1..=9).contains(digit)) {
+        Ok(input)
+    } else {
+        Err(SymbolicError::NoSolution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{monad_max, monad_min};
+
+    // one MONAD block: pushes its digit plus `push` when `pop` is false,
+    // otherwise pops, and wants its digit to be the popped one plus `check`
+    fn block(pop: bool, check: Reg, push: Reg) -> String {
+        [
+            "inp w", "mul x 0", "add x z", "mod x 26",
+            if pop { "div z 26" } else { "div z 1" },
+            &format!("add x {}", check),
+            "eql x w", "eql x 0", "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y",
+            "mul y 0", "add y w", &format!("add y {}", push), "mul y x", "add z y",
+        ].join("\n")
+    }
+
+    fn monad(blocks: &[(bool, Reg, Reg)]) -> Program {
+        let code: Vec<_> = blocks.iter().map(|&(pop, check, push)| block(pop, check, push)).collect();
+        Program::parse(&code.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn test_expr_simplify() {
+        let digit = Expr::add(Expr::Digit(0), Expr::Num(8)).unwrap();
+        let pushed = Expr::add(Expr::mul(Expr::Digit(1), Expr::Num(26)).unwrap(), digit.clone()).unwrap();
+
+        assert_eq!(digit, Expr::modulo(pushed.clone(), Expr::Num(26)));
+        assert_eq!(Some(Expr::Digit(1)), Expr::div(pushed, Expr::Num(26)));
+        assert_eq!(Expr::add(Expr::Digit(0), Expr::Num(3)), Expr::add(digit, Expr::Num(-5)));
+        assert_eq!(Some(Expr::Num(0)), Expr::eql(&Expr::add(Expr::Digit(2), Expr::Num(10)).unwrap(), &Expr::Digit(3)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = Reg::MAX;
+        let overflows = |code: &str| digit_constraints(&Program::parse(code).unwrap());
+
+        assert_eq!(Err(SymbolicError::Overflow(1)), overflows(&format!("add z {}\nadd z 1", max)));
+        assert_eq!(Err(SymbolicError::Overflow(2)), overflows(&format!("add z {}\nadd z -1\nmul z 2", max)));
+        assert_eq!(Err(SymbolicError::Overflow(2)), overflows(&format!("add z {}\nadd z -1\ndiv z -1", -max)));
+        // the constants gathered after a digit
+        assert_eq!(Err(SymbolicError::Overflow(2)), overflows(&format!("inp z\nadd z {}\nadd z 1", max)));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let program = monad(&[(false, 12, 4), (false, 11, 7), (true, -9, 2), (true, -3, 1)]);
+        let constraints = digit_constraints(&program).unwrap();
+        let shown: Vec<_> = constraints.iter().map(|constraint| constraint.to_string()).collect();
+
+        // d2 = d1 + 7 - 9 and d3 = d0 + 4 - 3
+        assert_eq!(vec!["d1 = d2 + 2", "d0 = d3 - 1"], shown);
+    }
+
+    #[test]
+    fn test_agrees_with_search() {
+        let program = monad(&[(false, 12, 4), (false, 11, 7), (true, -9, 2), (false, 10, 0), (true, 0, 3), (true, -3, 1)]);
+        let constraints = digit_constraints(&program).unwrap();
+        let to_string = |digits: Vec<Reg>| digits.iter().map(Reg::to_string).collect::<String>();
+
+        assert_eq!(monad_max(&program), max_input(&constraints, 6).ok().map(to_string));
+        assert_eq!(monad_min(&program), min_input(&constraints, 6).ok().map(to_string));
+    }
+
+    #[test]
+    fn test_no_solution() {
+        // the popped digit plus 9 can never be another digit
+        let program = monad(&[(false, 12, 9), (true, 0, 0)]);
+
+        assert_eq!(Err(SymbolicError::NoSolution), digit_constraints(&program));
+    }
+
+    #[test]
+    fn test_solve_limits() {
+        let chained = [Constraint { left: 0, right: 1, offset: 1 }, Constraint { left: 1, right: 2, offset: 1 }];
+        let apart = [Constraint { left: 0, right: 1, offset: 9 }];
+
+        // a chain may well have a solution, but it is not looked for
+        assert_eq!(Err(SymbolicError::Unsupported("d1 = d2 + 1 along with another constraint on its digits".to_string())), max_input(&chained, 3));
+        assert!(matches!(min_input(&chained, 3), Err(SymbolicError::Unsupported(_))));
+        assert!(matches!(max_input(&apart, 1), Err(SymbolicError::Unsupported(_))));
+        assert_eq!(Err(SymbolicError::NoSolution), max_input(&apart, 2));
+        assert_eq!(Err(SymbolicError::NoSolution), min_input(&apart, 2));
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let program = Program::parse("inp w\ninp x\ndiv w x\nmod w 0").unwrap();
+
+        assert!(matches!(digit_constraints(&program), Err(SymbolicError::Unsupported(_))));
+    }
+}
@@ -1,45 +1,61 @@
-use std::collections::HashSet;
+use std::fmt;
+use common::{Answer, Coord, Day, Grid, ParseError, Solution};
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-struct Coord {
-    r: usize,
-    c: usize,
+pub const DAY: Day = Day::new::<Solver>(25, "Sea Cucumber", concat!(env!("CARGO_MANIFEST_DIR"), "/input"));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Herd;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Herd::parse(input)
+    }
+
+    fn part_one(herd: &Self::Input) -> Answer {
+        let mut herd = herd.clone();
+
+        herd.steps_to_stasis().into()
+    }
+
+    fn part_two(_herd: &Self::Input) -> Answer {
+        // no part 2 for this day
+        Answer::None
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Space {
+    Empty,
+    East,
+    South,
 }
 
-impl Coord {
-    fn new(r: usize, c: usize) -> Self {
-        Self { r, c }
+impl fmt::Display for Space {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Space::Empty => write!(f, "."),
+            Space::East => write!(f, ">"),
+            Space::South => write!(f, "v"),
+        }
     }
 }
 
+#[derive(Clone)]
 pub struct Herd {
-    rows: usize,
-    cols: usize,
-    east: HashSet<Coord>,
-    south: HashSet<Coord>,
+    seafloor: Grid<Space>,
 }
 
 impl Herd {
-    pub fn parse(herd: &str) -> Self {
-        let lines: Vec<_> = herd.lines().collect();
-        let rows = lines.len();
-        let cols = lines[0].chars().count();
-
-        let (mut east, mut south) = (HashSet::new(), HashSet::new());
-        for (r, line) in lines.into_iter().enumerate() {
-            assert_eq!(cols, line.chars().count(), "inconsistent row length in input");
-            for (c, char) in line.chars().enumerate() {
-                match char {
-                    '>' => east.insert(Coord::new(r, c)),
-                    'v' => south.insert(Coord::new(r, c)),
-                    _ => continue,
-                };
-            }
-        }
-
-        Self {
-            rows, cols, east, south
-        }
+    pub fn parse(herd: &str) -> Result<Self, ParseError> {
+        let seafloor = Grid::parse(herd, |char| match char {
+            '>' => Ok(Space::East),
+            'v' => Ok(Space::South),
+            '.' => Ok(Space::Empty),
+            _ => Err(format!("unexpected `{}`", char)),
+        })?;
+
+        Ok(Self { seafloor })
     }
 
     pub fn steps_to_stasis(&mut self) -> u32 {
@@ -53,44 +69,33 @@ impl Herd {
     }
 
     fn step(&mut self) -> bool {
-        let (mut neast, mut nsouth) = (HashSet::new(), HashSet::new());
-        let mut stasis = true;
-
-        // east first
-        for sc in self.east.iter() {
-            let step = self.move_east(*sc);
-            if self.east.contains(&step) || self.south.contains(&step) {
-                neast.insert(*sc);
-            } else {
-                neast.insert(step);
-                stasis = false;
-            }
-        }
+        // east first, south last
+        let moved_east = self.step_herd(Space::East, 0, 1);
+        let moved_south = self.step_herd(Space::South, 1, 0);
 
-        self.east = neast;
-
-        // south last
-        for sc in self.south.iter() {
-            let step = self.move_south(*sc);
-            if self.east.contains(&step) || self.south.contains(&step) {
-                nsouth.insert(*sc);
-            } else {
-                nsouth.insert(step);
-                stasis = false;
-            }
-        }
-
-        self.south = nsouth;
-
-        stasis
+        !(moved_east || moved_south)
     }
 
-    fn move_east(&self, coord: Coord) -> Coord {
-        Coord::new(coord.r, (coord.c + 1) % self.cols)
+    // moves every cucumber of one herd that can, returning whether any did
+    fn step_herd(&mut self, herd: Space, rows: isize, cols: isize) -> bool {
+        let moves: Vec<(Coord, Coord)> = self.seafloor.iter()
+            .filter(|&(_, &space)| space == herd)
+            .map(|(from, _)| (from, self.seafloor.wrapping_offset(from, rows, cols)))
+            .filter(|&(_, to)| self.seafloor[to] == Space::Empty)
+            .collect();
+
+        for &(from, to) in moves.iter() {
+            self.seafloor[from] = Space::Empty;
+            self.seafloor[to] = herd;
+        }
+
+        !moves.is_empty()
     }
+}
 
-    fn move_south(&self, coord: Coord) -> Coord {
-        Coord::new((coord.r + 1) % self.rows, coord.c)
+impl fmt::Display for Herd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.seafloor)
     }
 }
 
@@ -99,8 +104,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn example() {
+        let herd = Solver::parse(include_str!("../input/example.txt")).unwrap();
+        assert_eq!(Answer::Number(58), Solver::part_one(&herd));
+        assert_eq!(Answer::None, Solver::part_two(&herd));
     }
 }
@@ -0,0 +1,123 @@
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use std::process;
+
+use common::cli::{bench_arg, bench_day, file_arg, load, part_arg, print_bench_table, verify_arg, verify_day};
+use common::Day;
+
+mod registry;
+
+fn main() {
+    let matches = App::new("Advent of Code 2021")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(SubCommand::with_name("run")
+            .about("run the solvers for one or every day")
+            .arg(Arg::with_name("DAY")
+                .short("d")
+                .long("day")
+                .takes_value(true)
+                .validator(validate_day)
+                .help("day to run"))
+            .arg(Arg::with_name("ALL")
+                .short("a")
+                .long("all")
+                .help("run every day"))
+            .group(ArgGroup::with_name("DAYS")
+                .args(&["DAY", "ALL"])
+                .required(true))
+            .arg(part_arg()
+                .help("part to run; runs both parts if omitted"))
+            .arg(file_arg()
+                .help("input file to take, either a path or the name of a file in the day's input directory; \
+                    defaults to the day's puzzle input, from $AOC_CACHE if set"))
+            .arg(verify_arg())
+            .arg(bench_arg()))
+        .get_matches();
+
+    if let ("run", Some(matches)) = matches.subcommand() {
+        if !run(matches) {
+            process::exit(1);
+        }
+    }
+}
+
+fn validate_day(s: String) -> Result<(), String> {
+    match s.parse::<u8>() {
+        Ok(day) if registry::day(day).is_some() => Ok(()),
+        _ => Err(String::from("day must be between 1 and 25")),
+    }
+}
+
+// returns whether every requested day could be run, and when verifying,
+// whether every answer matched
+fn run(matches: &ArgMatches) -> bool {
+    let days: Vec<&Day> = match matches.value_of("DAY") {
+        Some(day) => vec![registry::day(day.parse().unwrap()).unwrap()],
+        None => registry::DAYS.to_vec(),
+    };
+    let parts = match matches.value_of("PART") {
+        Some(part) => vec![part.parse::<u8>().unwrap()],
+        None => vec![1, 2],
+    };
+    let file = matches.value_of("FILE");
+
+    if matches.is_present("VERIFY") {
+        let failed: Vec<_> = days.iter()
+            .filter(|day| !verify_day(day, &parts, file))
+            .map(|day| day.number.to_string())
+            .collect();
+        if !failed.is_empty() {
+            eprintln!("verification failed for day {}", failed.join(", "));
+        }
+        return failed.is_empty();
+    }
+
+    if let Some(runs) = matches.value_of("BENCH") {
+        let runs = runs.parse().unwrap();
+        let mut success = true;
+        let mut rows = Vec::new();
+        for day in days {
+            // progress goes to stderr so that the table can be piped cleanly
+            eprintln!("benchmarking day {}: {}", day.number, day.title);
+            match bench_day(day, &parts, file, runs) {
+                Some(bench) => rows.push((day, bench)),
+                None => success = false,
+            }
+        }
+        print_bench_table(&rows);
+        return success;
+    }
+
+    // a single answer is printed bare, like the per-day binaries do
+    if days.len() == 1 && parts.len() == 1 {
+        return match load(days[0], file) {
+            Some(parsed) => {
+                println!("{}", parsed.part(parts[0]));
+                true
+            },
+            None => false,
+        };
+    }
+
+    let mut success = true;
+    for day in days {
+        println!("Day {}: {}", day.number, day.title);
+        let parsed = match load(day, file) {
+            Some(parsed) => parsed,
+            None => {
+                success = false;
+                continue;
+            },
+        };
+
+        for &part in parts.iter() {
+            let answer = parsed.part(part).to_string();
+            if answer.contains('\n') {
+                println!("  part {}:\n{}", part, answer);
+            } else {
+                println!("  part {}: {}", part, answer);
+            }
+        }
+    }
+
+    success
+}
@@ -0,0 +1,34 @@
+use common::Day;
+
+/// Every day of the calendar, in order.
+pub const DAYS: [&Day; 25] = [
+    &sonar_sweep::DAY,
+    &dive::DAY,
+    &binary_diagnostic::DAY,
+    &giant_squid::DAY,
+    &hydrothermal_venture::DAY,
+    &lanternfish::DAY,
+    &the_treachery_of_whales::DAY,
+    &seven_segment_search::DAY,
+    &smoke_basin::DAY,
+    &syntax_scoring::DAY,
+    &dumbo_octopus::DAY,
+    &passage_pathing::DAY,
+    &transparent_origami::DAY,
+    &extended_polymerization::DAY,
+    &chiton::DAY,
+    &packet_decoder::DAY,
+    &trick_shot::DAY,
+    &snailfish::DAY,
+    &beacon_scanner::DAY,
+    &trench_map::DAY,
+    &dirac_dice::DAY,
+    &reactor_reboot::DAY,
+    &amphipod::DAY,
+    &arithmetic_logic_unit::DAY,
+    &sea_cucumber::DAY,
+];
+
+pub fn day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().copied().find(|day| day.number == number)
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::Answer;
+
+/// The expected answers for a day, read from the `answers.toml` next to its
+/// `input` directory.
+///
+/// Each table is named after an input file without its extension, so the
+/// answers for `input/example.txt` live under `[example]`:
+///
+/// ```toml
+/// [example]
+/// part1 = 7
+/// part2 = 5
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    files: HashMap<String, Expected>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Expected {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, toml::de::Error> {
+        Ok(Self { files: toml::from_str(s)? })
+    }
+
+    /// The expected answer to one part for the named input file, if known.
+    pub fn expected(&self, file: &str, part: u8) -> Option<&Answer> {
+        let expected = self.files.get(file)?;
+        match part {
+            1 => expected.part1.as_ref(),
+            2 => expected.part2.as_ref(),
+            _ => panic!("part must be either 1 or 2"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse(r#"
+            [input]
+            part1 = 1451
+            part2 = "none"
+
+            [example]
+            part2 = '''
+            #.#
+            .#.'''
+        "#).unwrap();
+
+        assert_eq!(Some(&Answer::Number(1451)), answers.expected("input", 1));
+        assert_eq!(Some(&Answer::from("none")), answers.expected("input", 2));
+        assert_eq!(None, answers.expected("example", 1));
+        assert_eq!("            #.#\n            .#.", answers.expected("example", 2).unwrap().to_string());
+        assert_eq!(None, answers.expected("example1", 1));
+    }
+}
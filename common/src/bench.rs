@@ -0,0 +1,100 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::{Day, ParseError};
+
+/// Wall times for repeated runs of one part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarize a non-empty set of samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarize");
+        samples.sort();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Self { min: samples[0], median, max: samples[samples.len() - 1] }
+    }
+}
+
+/// Timings for one day: its input is parsed once, then each part is run
+/// repeatedly against the parsed input.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub parse: Duration,
+    pub parts: Vec<(u8, Stats)>,
+}
+
+/// Time parsing `input` once and each of `parts` `runs` times.
+pub fn bench(day: &Day, input: &str, parts: &[u8], runs: usize) -> Result<Bench, ParseError> {
+    let start = Instant::now();
+    let parsed = day.parse(input)?;
+    let parse = start.elapsed();
+
+    let parts = parts.iter().map(|&part| {
+        let samples = (0..runs).map(|_| {
+            let start = Instant::now();
+            parsed.part(part);
+            start.elapsed()
+        }).collect();
+        (part, Stats::from_samples(samples))
+    }).collect();
+
+    Ok(Bench { parse, parts })
+}
+
+/// A duration in the most readable unit, like `1.25ms`.
+pub struct Pretty(pub Duration);
+
+impl fmt::Display for Pretty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nanos = self.0.as_nanos() as f64;
+        let s = if nanos < 1e3 {
+            format!("{}ns", nanos)
+        } else if nanos < 1e6 {
+            format!("{:.1}µs", nanos / 1e3)
+        } else if nanos < 1e9 {
+            format!("{:.2}ms", nanos / 1e6)
+        } else {
+            format!("{:.2}s", nanos / 1e9)
+        };
+        // pad to the requested width, which a plain `write!` would ignore
+        f.pad(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples() {
+        let ms = Duration::from_millis;
+
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(Stats { min: ms(1), median: ms(3), max: ms(5) }, stats);
+
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(8)]);
+        assert_eq!(Stats { min: ms(1), median: ms(3), max: ms(8) }, stats);
+    }
+
+    #[test]
+    fn pretty_durations() {
+        assert_eq!("512ns", Pretty(Duration::from_nanos(512)).to_string());
+        assert_eq!("12.3µs", Pretty(Duration::from_nanos(12_345)).to_string());
+        assert_eq!("1.25ms", Pretty(Duration::from_micros(1_250)).to_string());
+        assert_eq!("2.50s", Pretty(Duration::from_millis(2_500)).to_string());
+        assert_eq!("  1.25ms", format!("{:>8}", Pretty(Duration::from_micros(1_250))));
+    }
+}
@@ -0,0 +1,256 @@
+use clap::{App, Arg};
+use std::fs;
+use std::process;
+
+use crate::bench::{self, Bench, Pretty};
+use crate::input::InputStore;
+use crate::{Answers, Day, Parsed};
+
+/// The `--part` argument shared by every runner.
+pub fn part_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("PART")
+        .short("p")
+        .long("part")
+        .takes_value(true)
+        .validator(validate_part)
+        .help("part of this day to run")
+}
+
+/// The `--file` argument shared by every runner.
+pub fn file_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("FILE")
+        .short("f")
+        .long("file")
+        .takes_value(true)
+        .help("input file to take; defaults to the day's puzzle input")
+}
+
+/// The `--verify` flag shared by every runner.
+pub fn verify_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("VERIFY")
+        .short("v")
+        .long("verify")
+        .help("check answers against the day's answers.toml instead of printing them")
+}
+
+/// The `--bench` argument shared by every runner.
+pub fn bench_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("BENCH")
+        .short("b")
+        .long("bench")
+        .takes_value(true)
+        .value_name("N")
+        .validator(validate_runs)
+        .conflicts_with("VERIFY")
+        .help("time parsing once and each part N times instead of printing answers")
+}
+
+fn validate_runs(s: String) -> Result<(), String> {
+    match s.parse::<usize>() {
+        Ok(runs) if runs > 0 => Ok(()),
+        _ => Err(String::from("the number of runs must be a positive integer")),
+    }
+}
+
+fn validate_part(s: String) -> Result<(), String> {
+    if s == "1" || s == "2" {
+        Ok(())
+    } else {
+        Err(String::from("part must be either 1 or 2"))
+    }
+}
+
+/// Entry point for a single day's binary: parses `--part`/`--file` and prints
+/// the answer for that part. Without `--file`, the day's puzzle input is
+/// found by an `InputStore` configured from the environment.
+///
+/// With `--verify`, checks the given part (or both) against the day's
+/// `answers.toml` instead, exiting non-zero on any mismatch. With `--bench N`,
+/// times the given part (or both) N times and prints a table.
+pub fn run_day(day: &Day) {
+    let matches = App::new(day.title)
+        .arg(part_arg().required_unless_one(&["VERIFY", "BENCH"]))
+        .arg(file_arg())
+        .arg(verify_arg())
+        .arg(bench_arg())
+        .get_matches();
+
+    let part = matches.value_of("PART").map(|part| part.parse::<u8>().unwrap());
+    let file = matches.value_of("FILE");
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if matches.is_present("VERIFY") {
+        if !verify_day(day, &parts, file) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(runs) = matches.value_of("BENCH") {
+        match bench_day(day, &parts, file, runs.parse().unwrap()) {
+            Some(bench) => print_bench_table(&[(day, bench)]),
+            None => process::exit(1),
+        }
+        return;
+    }
+
+    match load(day, file) {
+        Some(parsed) => println!("{}", parsed.part(part.unwrap())),
+        None => process::exit(1),
+    }
+}
+
+// reads a day's input, or its puzzle input if no file is given, reporting any
+// failure on stderr
+fn read(day: &Day, file: Option<&str>) -> Option<String> {
+    let input = match file {
+        Some(file) => day.read_input(file)
+            .map_err(|e| format!("could not read {}: {}", day.input_path(file).display(), e)),
+        None => InputStore::from_env().resolve(day)
+            .map_err(|e| format!("could not find the input for day {}: {}", day.number, e)),
+    };
+
+    match input {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        },
+    }
+}
+
+// what to call a day's input in error messages
+fn describe(day: &Day, file: Option<&str>) -> String {
+    match file {
+        Some(file) => day.input_path(file).display().to_string(),
+        None => format!("the input for day {}", day.number),
+    }
+}
+
+/// Reads and parses a day's input, or its puzzle input if no file is given,
+/// reporting any failure on stderr.
+pub fn load(day: &Day, file: Option<&str>) -> Option<Box<dyn Parsed>> {
+    let input = read(day, file)?;
+
+    match day.parse(&input) {
+        Ok(parsed) => Some(parsed),
+        Err(e) => {
+            eprintln!("could not parse {}: {}", describe(day, file), e);
+            None
+        },
+    }
+}
+
+/// Runs the given parts of a day and compares them against its
+/// `answers.toml`, printing a line per part. Returns whether every part
+/// matched.
+pub fn verify_day(day: &Day, parts: &[u8], file: Option<&str>) -> bool {
+    println!("Day {}: {}", day.number, day.title);
+
+    let path = day.answers_path();
+    let answers = match fs::read_to_string(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("could not read {}: {}", path.display(), e);
+            return false;
+        },
+    };
+    let answers = match Answers::parse(&answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("could not parse {}: {}", path.display(), e);
+            return false;
+        },
+    };
+
+    let parsed = match load(day, file) {
+        Some(parsed) => parsed,
+        None => return false,
+    };
+
+    // answers are keyed by the input's file name, so a path to `input.txt`
+    // anywhere checks against `[input]`, as does the puzzle input
+    let file = file.unwrap_or("input");
+    let input_path = day.input_path(file);
+    let name = input_path.file_stem().and_then(|name| name.to_str()).unwrap_or(file);
+
+    let mut success = true;
+    for &part in parts {
+        let answer = parsed.part(part).to_string();
+        match answers.expected(name, part) {
+            // compared as displayed, so a large number stored as text in the
+            // toml still matches
+            Some(expected) if expected.to_string() == answer => {
+                println!("  part {}: ok", part);
+            },
+            Some(expected) => {
+                println!("  part {}: FAILED\n    expected: {}\n    got:      {}", part, expected, answer);
+                success = false;
+            },
+            None => {
+                println!("  part {}: FAILED\n    no answer for [{}] in {}", part, name, path.display());
+                success = false;
+            },
+        }
+    }
+
+    success
+}
+
+/// Times a day's parse and the given parts, reporting any failure to read or
+/// parse its input on stderr.
+pub fn bench_day(day: &Day, parts: &[u8], file: Option<&str>, runs: usize) -> Option<Bench> {
+    let input = read(day, file)?;
+
+    match bench::bench(day, &input, parts, runs) {
+        Ok(bench) => Some(bench),
+        Err(e) => {
+            eprintln!("could not parse {}: {}", describe(day, file), e);
+            None
+        },
+    }
+}
+
+/// Prints a table of timings, one row per day, with a row of totals when
+/// there is more than one day.
+pub fn print_bench_table(rows: &[(&Day, Bench)]) {
+    let parts: Vec<u8> = match rows.first() {
+        Some((_, bench)) => bench.parts.iter().map(|&(part, _)| part).collect(),
+        None => return,
+    };
+
+    let mut header = format!("{:>5}  {:<24} {:>9}", "day", "title", "parse");
+    for part in parts.iter() {
+        for stat in ["min", "median", "max"] {
+            header.push_str(&format!(" {:>9}", format!("{} {}", part, stat)));
+        }
+    }
+    println!("{}", header);
+    println!("{}", "-".repeat(header.chars().count()));
+
+    let row = |day: &str, title: &str, parse, stats: &[bench::Stats]| {
+        let mut row = format!("{:>5}  {:<24} {:>9}", day, title, Pretty(parse));
+        for stats in stats {
+            row.push_str(&format!(" {:>9} {:>9} {:>9}", Pretty(stats.min), Pretty(stats.median), Pretty(stats.max)));
+        }
+        println!("{}", row);
+    };
+
+    for (day, bench) in rows {
+        let stats: Vec<_> = bench.parts.iter().map(|&(_, stats)| stats).collect();
+        row(&day.number.to_string(), day.title, bench.parse, &stats);
+    }
+
+    if rows.len() > 1 {
+        let parse = rows.iter().map(|(_, bench)| bench.parse).sum();
+        let totals: Vec<_> = (0..parts.len()).map(|i| bench::Stats {
+            min: rows.iter().map(|(_, bench)| bench.parts[i].1.min).sum(),
+            median: rows.iter().map(|(_, bench)| bench.parts[i].1.median).sum(),
+            max: rows.iter().map(|(_, bench)| bench.parts[i].1.max).sum(),
+        }).collect();
+        row("total", "", parse, &totals);
+    }
+}
@@ -0,0 +1,242 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, MulAssign};
+
+/// The count type of a `Counter`, implemented for the primitive integers.
+pub trait Count: Copy + Ord + Add<Output = Self> + AddAssign + Mul<Output = Self> + MulAssign + Sum {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! count {
+    ($($t:ty),*) => {$(
+        impl Count for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+    )*};
+}
+
+count!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Counts of how many times each item has been seen.
+///
+/// Counts are `u32` unless a wider type is needed, like `Counter<char, u128>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T, N = u32>
+    where T: Eq + Hash, N: Count
+{
+    counts: HashMap<T, N>,
+}
+
+impl<T, N> Default for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N> Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_count(item, N::ONE);
+    }
+
+    pub fn add_count(&mut self, item: T, count: N) {
+        let entry = self.counts.entry(item).or_insert(N::ZERO);
+        *entry += count;
+    }
+
+    /// How many times `item` has been seen, which is zero if it never was.
+    pub fn count(&self, item: &T) -> N {
+        self.counts.get(item).copied().unwrap_or(N::ZERO)
+    }
+
+    /// The sum of every count.
+    pub fn total(&self) -> N {
+        self.counts.values().copied().sum()
+    }
+
+    /// The number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &T> {
+        self.counts.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &N)> {
+        self.counts.iter()
+    }
+
+    pub fn with_count_ge(&self, min: N) -> Vec<&T> {
+        self.counts.iter().filter_map(|(key, value)| {
+            if *value >= min {
+                Some(key)
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+    /// Add every count in `other` to this counter.
+    pub fn merge(&mut self, other: Self) {
+        for (item, count) in other {
+            self.add_count(item, count);
+        }
+    }
+
+    /// Multiply every count by `factor`.
+    pub fn scale(&mut self, factor: N) {
+        self.counts.values_mut().for_each(|count| *count *= factor);
+    }
+}
+
+impl<T, N> Counter<T, N>
+    where T: Eq + Hash + Ord, N: Count
+{
+    /// The `n` most common items with their counts, most common first. Items
+    /// with equal counts are ordered by the items themselves, so the result
+    /// does not depend on hashing.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, N)> {
+        let mut counts: Vec<_> = self.counts.iter().map(|(item, &count)| (item, count)).collect();
+        counts.sort_by_key(|&(item, count)| (Reverse(count), item));
+        counts.truncate(n);
+        counts
+    }
+
+    /// The most common item, the smallest one if there is a tie.
+    pub fn most_frequent(&self) -> Option<&T> {
+        self.counts.iter()
+            .min_by_key(|&(item, &count)| (Reverse(count), item))
+            .map(|(item, _)| item)
+    }
+
+    /// The least common item, the smallest one if there is a tie.
+    pub fn least_frequent(&self) -> Option<&T> {
+        self.counts.iter()
+            .min_by_key(|&(item, &count)| (count, item))
+            .map(|(item, _)| item)
+    }
+}
+
+impl<T, N> Add for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self.merge(other);
+        self
+    }
+}
+
+impl<T, N> Mul<N> for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    type Output = Self;
+
+    fn mul(mut self, factor: N) -> Self {
+        self.scale(factor);
+        self
+    }
+}
+
+impl<T, N> Sum for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(), |sum, counter| sum + counter)
+    }
+}
+
+impl<T, N> IntoIterator for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    type Item = (T, N);
+    type IntoIter = std::collections::hash_map::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T, N> FromIterator<T> for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut c = Self::new();
+
+        for i in iter {
+            // `c.add(i)` would pick `Add::add`, which takes `c` by value
+            Self::add(&mut c, i);
+        }
+
+        c
+    }
+}
+
+impl<T, N> FromIterator<(T, N)> for Counter<T, N>
+    where T: Eq + Hash, N: Count
+{
+    fn from_iter<I: IntoIterator<Item=(T, N)>>(iter: I) -> Self {
+        let mut c = Self::new();
+
+        for (i, count) in iter {
+            c.add_count(i, count);
+        }
+
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let counter: Counter<char> = "hello".chars().collect();
+        assert_eq!(2, counter.count(&'l'));
+        assert_eq!(0, counter.count(&'z'));
+        assert_eq!(5, counter.total());
+        assert_eq!(4, counter.len());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: Counter<char, u128> = "aab".chars().collect();
+        let b: Counter<char, u128> = "bc".chars().collect();
+
+        let sum = a.clone() + b;
+        assert_eq!(vec![(&'a', 2), (&'b', 2), (&'c', 1)], sum.most_common(3));
+
+        let scaled = a * 1_000_000_000_000;
+        assert_eq!(2_000_000_000_000, scaled.count(&'a'));
+        assert_eq!(3_000_000_000_000, scaled.total());
+    }
+
+    #[test]
+    fn most_common_breaks_ties_by_item() {
+        let counter: Counter<char> = "cbabcd".chars().collect();
+        assert_eq!(vec![(&'b', 2), (&'c', 2)], counter.most_common(2));
+        assert_eq!(vec![(&'b', 2), (&'c', 2), (&'a', 1), (&'d', 1)], counter.most_common(10));
+        assert_eq!(Some(&'b'), counter.most_frequent());
+        assert_eq!(Some(&'a'), counter.least_frequent());
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use crate::{Answer, ParseError, Solution};
+
+/// A single puzzle day, as exposed by each day's library crate.
+///
+/// Every day crate defines a `DAY` constant of this type so that both its own
+/// binary and the workspace `aoc` runner can call into the same solvers.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    // directory holding the day's `input.txt`, `example.txt`, etc.
+    pub input_dir: &'static str,
+    parse: fn(&str) -> Result<Box<dyn Parsed>, ParseError>,
+}
+
+/// A day's input after parsing, ready to answer either part.
+pub trait Parsed {
+    fn part_one(&self) -> Answer;
+    fn part_two(&self) -> Answer;
+
+    fn part(&self, part: u8) -> Answer {
+        match part {
+            1 => self.part_one(),
+            2 => self.part_two(),
+            _ => panic!("part must be either 1 or 2"),
+        }
+    }
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+    solution: PhantomData<S>,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn part_one(&self) -> Answer {
+        S::part_one(&self.input)
+    }
+
+    fn part_two(&self) -> Answer {
+        S::part_two(&self.input)
+    }
+}
+
+fn parse_boxed<S>(input: &str) -> Result<Box<dyn Parsed>, ParseError>
+    where S: Solution + 'static, S::Input: 'static
+{
+    let input = S::parse(input)?;
+    Ok(Box::new(ParsedInput::<S> { input, solution: PhantomData }))
+}
+
+impl Day {
+    pub const fn new<S>(number: u8, title: &'static str, input_dir: &'static str) -> Self
+        where S: Solution + 'static, S::Input: 'static
+    {
+        Self { number, title, input_dir, parse: parse_boxed::<S> }
+    }
+
+    /// Parse the contents of an input file for this day.
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        (self.parse)(input).map_err(|e| e.with_day(self.number))
+    }
+
+    /// Parse the contents of an input file and run one part against it.
+    pub fn run(&self, part: u8, input: &str) -> Result<Answer, ParseError> {
+        Ok(self.parse(input)?.part(part))
+    }
+
+    /// Resolve an input argument for this day. Paths to existing files are
+    /// used as-is; anything else names a file in the day's input directory,
+    /// so `example` resolves to `<input_dir>/example.txt`.
+    pub fn input_path(&self, file: &str) -> PathBuf {
+        let path = Path::new(file);
+        if path.is_file() {
+            path.to_path_buf()
+        } else {
+            Path::new(self.input_dir).join(format!("{}.txt", file))
+        }
+    }
+
+    pub fn read_input(&self, file: &str) -> io::Result<String> {
+        fs::read_to_string(self.input_path(file))
+    }
+
+    /// The day's `answers.toml`, which sits next to its input directory.
+    pub fn answers_path(&self) -> PathBuf {
+        Path::new(self.input_dir).with_file_name("answers.toml")
+    }
+}
@@ -0,0 +1,293 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::ParseError;
+
+/// A position in a `Grid`, counting rows down and columns across from the top
+/// left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coord {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+// up, left, right, down
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+// every surrounding cell, in reading order
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    ( 0, -1),          ( 0, 1),
+    ( 1, -1), ( 1, 0), ( 1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+        where T: Clone
+    {
+        Self { rows, cols, cells: vec![fill; rows * cols] }
+    }
+
+    /// A grid whose cells are `f` of their coordinates.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| Coord::new(row, col)))
+            .map(&mut f)
+            .collect();
+        Self { rows, cols, cells }
+    }
+
+    /// A grid from a list of rows, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(rows.iter().all(|row| row.len() == cols), "rows must all have the same length");
+
+        Self { rows: rows.len(), cols, cells: rows.into_iter().flatten().collect() }
+    }
+
+    /// Parse a map with a character per cell, like `#..#` or `2199943`.
+    ///
+    /// `cell` converts each character, explaining why when it cannot. Every
+    /// line must be the same, non-zero length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Result<T, String>) -> Result<Self, ParseError> {
+        let mut cols = None;
+        let mut rows = 0;
+        let mut cells = Vec::new();
+
+        for line in input.lines() {
+            let len = line.chars().count();
+            if len == 0 || cols.is_some_and(|cols| cols != len) {
+                return Err(ParseError::at(input, line, "rows must all have the same, non-zero length"));
+            }
+            cols = Some(len);
+            rows += 1;
+
+            for (i, c) in line.char_indices() {
+                cells.push(cell(c).map_err(|e| ParseError::at(input, &line[i..], e))?);
+            }
+        }
+
+        match cols {
+            Some(cols) => Ok(Self { rows, cols, cells }),
+            None => Err(ParseError::new(1, 1, "the grid is empty")),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, c: Coord) -> bool {
+        c.row < self.rows && c.col < self.cols
+    }
+
+    pub fn get(&self, c: Coord) -> Option<&T> {
+        if self.contains(c) {
+            Some(&self.cells[c.row * self.cols + c.col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, c: Coord) -> Option<&mut T> {
+        if self.contains(c) {
+            Some(&mut self.cells[c.row * self.cols + c.col])
+        } else {
+            None
+        }
+    }
+
+    /// Every coordinate, in reading order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| Coord::new(row, col)))
+    }
+
+    /// Every cell with its coordinate, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// The cells of column `col`, top to bottom. Panics if there is no such
+    /// column, as `row` does.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.cols, "column {} is out of bounds for a grid of {} columns", col, self.cols);
+        self.cells.iter().skip(col).step_by(self.cols).take(self.rows)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { rows: self.rows, cols: self.cols, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// The coordinate offset from `c` by the given rows and columns, if it is
+    /// inside the grid.
+    pub fn offset(&self, c: Coord, rows: isize, cols: isize) -> Option<Coord> {
+        let row = c.row.checked_add_signed(rows)?;
+        let col = c.col.checked_add_signed(cols)?;
+        Some(Coord::new(row, col)).filter(|&c| self.contains(c))
+    }
+
+    /// The coordinate offset from `c` by the given rows and columns, wrapping
+    /// around the edges of the grid.
+    pub fn wrapping_offset(&self, c: Coord, rows: isize, cols: isize) -> Coord {
+        let wrap = |n: usize, by: isize, size: usize| {
+            (n as isize + by).rem_euclid(size as isize) as usize
+        };
+        Coord::new(wrap(c.row, rows, self.rows), wrap(c.col, cols, self.cols))
+    }
+
+    /// The up to 4 cells above, below, left and right of `c`.
+    pub fn neighbors4(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&(rows, cols)| self.offset(c, rows, cols))
+    }
+
+    /// The up to 8 cells surrounding `c`, including diagonals.
+    pub fn neighbors8(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        SURROUNDING.iter().filter_map(move |&(rows, cols)| self.offset(c, rows, cols))
+    }
+
+    /// The 4 cells above, below, left and right of `c`, wrapping around the
+    /// edges as if the grid were a torus.
+    pub fn wrapping_neighbors4(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        ORTHOGONAL.iter().map(move |&(rows, cols)| self.wrapping_offset(c, rows, cols))
+    }
+
+    /// The 8 cells surrounding `c`, wrapping around the edges as if the grid
+    /// were a torus.
+    pub fn wrapping_neighbors8(&self, c: Coord) -> impl Iterator<Item = Coord> + '_ {
+        SURROUNDING.iter().map(move |&(rows, cols)| self.wrapping_offset(c, rows, cols))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coord) -> &T {
+        self.get(c).unwrap_or_else(|| panic!("{:?} is outside the grid", c))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, c: Coord) -> &mut T {
+        self.get_mut(c).unwrap_or_else(|| panic!("{:?} is outside the grid", c))
+    }
+}
+
+/// Displays a row per line, with no trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.iter_rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or_else(|| format!("`{}` is not a digit", c)))
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((2, 3), (grid.rows(), grid.cols()));
+        assert_eq!(Some(&6), grid.get(Coord::new(1, 2)));
+        assert_eq!(None, grid.get(Coord::new(2, 0)));
+        assert_eq!("123\n456", grid.to_string());
+
+        let error = digits("123\n4x6").unwrap_err();
+        assert_eq!((2, 2), (error.line(), error.column()));
+        let error = digits("123\n45").unwrap_err();
+        assert_eq!((2, 1), (error.line(), error.column()));
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(2, grid.iter_rows().count());
+        assert_eq!(Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]), grid);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is out of bounds")]
+    fn column_out_of_bounds() {
+        let grid = digits("123\n456").unwrap();
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 4, ());
+        let corner = Coord::new(0, 0);
+        let middle = Coord::new(1, 1);
+
+        assert_eq!(vec![Coord::new(0, 1), Coord::new(1, 0)], grid.neighbors4(corner).collect::<Vec<_>>());
+        assert_eq!(4, grid.neighbors4(middle).count());
+        assert_eq!(3, grid.neighbors8(corner).count());
+        assert_eq!(8, grid.neighbors8(middle).count());
+    }
+
+    #[test]
+    fn wrapping_neighbors() {
+        let grid = Grid::new(3, 4, ());
+        let corner = Coord::new(0, 0);
+
+        assert_eq!(
+            vec![Coord::new(2, 0), Coord::new(0, 3), Coord::new(0, 1), Coord::new(1, 0)],
+            grid.wrapping_neighbors4(corner).collect::<Vec<_>>()
+        );
+        assert_eq!(Coord::new(2, 3), grid.wrapping_neighbors8(corner).next().unwrap());
+        assert_eq!(Coord::new(0, 0), grid.wrapping_offset(Coord::new(2, 3), 1, 1));
+    }
+}
@@ -0,0 +1,250 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::Day;
+
+/// The puzzle year every input belongs to.
+pub const YEAR: u16 = 2021;
+
+/// Where inputs are fetched from unless `AOC_BASE_URL` says otherwise.
+#[cfg(feature = "fetch")]
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Finds a day's puzzle input when no file is given.
+///
+/// A day's input is looked for in the cache directory first, as
+/// `<cache>/2021/day15.txt`. If it is not there and a fetcher is configured,
+/// which needs the `fetch` feature, it is downloaded and saved to the cache.
+/// Otherwise the hand-placed `input` file in the day's input directory is
+/// used.
+#[derive(Debug, Clone, Default)]
+pub struct InputStore {
+    cache_dir: Option<PathBuf>,
+    #[cfg(feature = "fetch")]
+    fetcher: Option<Fetcher>,
+}
+
+/// Downloads inputs from an Advent of Code server, logged in with a session
+/// token.
+#[cfg(feature = "fetch")]
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Read(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    // ureq's errors already name the url
+    #[cfg(feature = "fetch")]
+    Fetch(Box<ureq::Error>),
+    // nothing was cached, nothing could be fetched, and there is no
+    // hand-placed input
+    Missing { cached: Option<PathBuf>, local: PathBuf },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "could not read {}: {}", path.display(), e),
+            Self::Write(path, e) => write!(f, "could not cache input at {}: {}", path.display(), e),
+            #[cfg(feature = "fetch")]
+            Self::Fetch(e) => write!(f, "could not fetch {}", e),
+            #[cfg(feature = "fetch")]
+            Self::Missing { cached: Some(cached), local } => write!(
+                f, "no input at {} or {}, and AOC_SESSION is not set to fetch it",
+                cached.display(), local.display()
+            ),
+            #[cfg(not(feature = "fetch"))]
+            Self::Missing { cached: Some(cached), local } => write!(f, "no input at {} or {}", cached.display(), local.display()),
+            Self::Missing { cached: None, local } => write!(f, "no input at {}", local.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+#[cfg(feature = "fetch")]
+impl Fetcher {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self { base_url, session: session.into() }
+    }
+
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day)
+    }
+
+    pub fn fetch(&self, day: u8) -> Result<String, InputError> {
+        let fetch_error = |e| InputError::Fetch(Box::new(e));
+
+        let response = ureq::get(&self.url(day))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(fetch_error)?;
+        response.into_string().map_err(|e| fetch_error(e.into()))
+    }
+}
+
+impl InputStore {
+    pub fn new(cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            cache_dir,
+            #[cfg(feature = "fetch")]
+            fetcher: None,
+        }
+    }
+
+    #[cfg(feature = "fetch")]
+    pub fn with_fetcher(self, fetcher: Fetcher) -> Self {
+        Self { fetcher: Some(fetcher), ..self }
+    }
+
+    /// A store configured by the environment: `AOC_CACHE` is the cache
+    /// directory, and with the `fetch` feature, setting `AOC_SESSION` enables
+    /// fetching from `AOC_BASE_URL`, which defaults to the real site.
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).ok().filter(|value: &String| !value.is_empty());
+        let store = Self::new(var("AOC_CACHE").map(PathBuf::from));
+
+        #[cfg(feature = "fetch")]
+        if let Some(session) = var("AOC_SESSION") {
+            let base_url = var("AOC_BASE_URL").unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+            return store.with_fetcher(Fetcher::new(base_url, session));
+        }
+        store
+    }
+
+    /// Where a day's input is cached, if there is a cache.
+    pub fn cache_path(&self, day: u8) -> Option<PathBuf> {
+        self.cache_dir.as_ref().map(|dir| dir.join(YEAR.to_string()).join(format!("day{}.txt", day)))
+    }
+
+    /// The day's puzzle input, from the cache, the fetcher or the day's own
+    /// `input` file, in that order.
+    pub fn resolve(&self, day: &Day) -> Result<String, InputError> {
+        let cached = self.cache_path(day.number);
+
+        if let Some(path) = cached.as_ref().filter(|path| path.is_file()) {
+            return fs::read_to_string(path).map_err(|e| InputError::Read(path.clone(), e));
+        }
+
+        #[cfg(feature = "fetch")]
+        if let Some(fetcher) = &self.fetcher {
+            let input = fetcher.fetch(day.number)?;
+            if let Some(path) = &cached {
+                let written = match path.parent() {
+                    Some(dir) => fs::create_dir_all(dir).and_then(|_| fs::write(path, &input)),
+                    None => fs::write(path, &input),
+                };
+                written.map_err(|e| InputError::Write(path.clone(), e))?;
+            }
+            return Ok(input);
+        }
+
+        let local = day.input_path("input");
+        if local.is_file() {
+            return fs::read_to_string(&local).map_err(|e| InputError::Read(local, e));
+        }
+
+        Err(InputError::Missing { cached, local })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "fetch")]
+    use std::io::{BufRead, BufReader, Write};
+    #[cfg(feature = "fetch")]
+    use std::net::TcpListener;
+    #[cfg(feature = "fetch")]
+    use std::thread;
+
+    use crate::{Answer, ParseError, Solution};
+
+    struct Echo;
+
+    impl Solution for Echo {
+        type Input = String;
+
+        fn parse(input: &str) -> Result<String, ParseError> {
+            Ok(input.to_string())
+        }
+
+        fn part_one(input: &String) -> Answer {
+            input.as_str().into()
+        }
+
+        fn part_two(_: &String) -> Answer {
+            Answer::None
+        }
+    }
+
+    const DAY: Day = Day::new::<Echo>(15, "Echo", "/nonexistent/input");
+
+    // serves `body` to a single request, sending back the request line and
+    // cookie it was asked with
+    #[cfg(feature = "fetch")]
+    fn stub_server(body: &'static str) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut cookie = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                if request.is_empty() {
+                    request = line.trim_end().to_string();
+                } else if let Some(value) = line.strip_prefix("Cookie: ") {
+                    cookie = value.trim_end().to_string();
+                }
+                line.clear();
+            }
+
+            write!(reader.get_mut(), "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+            (request, cookie)
+        });
+
+        (base_url, server)
+    }
+
+    #[test]
+    #[cfg(feature = "fetch")]
+    fn fetches_then_caches() {
+        let cache = env::temp_dir().join(format!("aoc-input-test-{}", std::process::id()));
+        let (base_url, server) = stub_server("1163751742\n");
+        let store = InputStore::new(Some(cache.clone())).with_fetcher(Fetcher::new(base_url + "/", "secret"));
+
+        assert_eq!("1163751742\n", store.resolve(&DAY).unwrap());
+        let (request, cookie) = server.join().unwrap();
+        assert_eq!("GET /2021/day/15/input HTTP/1.1", request);
+        assert_eq!("session=secret", cookie);
+
+        // the server is gone, so this can only come from the cache
+        let cached = cache.join("2021").join("day15.txt");
+        assert_eq!(Some(cached.clone()), store.cache_path(15));
+        assert_eq!("1163751742\n", fs::read_to_string(&cached).unwrap());
+        assert_eq!("1163751742\n", store.resolve(&DAY).unwrap());
+
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn missing() {
+        let store = InputStore::new(Some(PathBuf::from("/nonexistent/cache")));
+        let error = store.resolve(&DAY).unwrap_err();
+
+        assert!(matches!(error, InputError::Missing { cached: Some(_), .. }));
+        assert!(error.to_string().contains("/nonexistent/cache/2021/day15.txt"));
+    }
+}
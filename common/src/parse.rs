@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input, and where in the input it was found.
+///
+/// Lines and columns count from 1; columns count characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    day: Option<u8>,
+    line: usize,
+    column: usize,
+    explanation: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, explanation: impl Into<String>) -> Self {
+        Self { day: None, line, column, explanation: explanation.into() }
+    }
+
+    /// An error at `fragment`, which must be a slice of `input`.
+    pub fn at(input: &str, fragment: &str, explanation: impl Into<String>) -> Self {
+        let (line, column) = position(input, fragment);
+        Self::new(line, column, explanation)
+    }
+
+    /// Move an error found while parsing `fragment`, a slice of `input`, so
+    /// that its position is relative to the whole of `input`.
+    pub fn within(self, input: &str, fragment: &str) -> Self {
+        let (line, column) = position(input, fragment);
+        let column = if self.line == 1 { column + self.column - 1 } else { self.column };
+        Self { line: line + self.line - 1, column, ..self }
+    }
+
+    pub fn with_day(self, day: u8) -> Self {
+        Self { day: Some(day), ..self }
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn explanation(&self) -> &str {
+        &self.explanation
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.explanation)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse `fragment`, a slice of `input`, reporting where it sits in `input` if
+/// it cannot be parsed.
+pub fn parse_at<T>(input: &str, fragment: &str) -> Result<T, ParseError>
+    where T: FromStr, T::Err: fmt::Display
+{
+    fragment.parse::<T>().map_err(|e| {
+        ParseError::at(input, fragment, format!("could not parse `{}`: {}", fragment, e))
+    })
+}
+
+// line and column of the start of `fragment` within `input`
+fn position(input: &str, fragment: &str) -> (usize, usize) {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    assert!(offset <= input.len(), "fragment is not a slice of the input");
+
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_fragment() {
+        let input = "abc\ndef\nghi";
+        let error = ParseError::at(input, &input[5..7], "bad");
+        assert_eq!((2, 2), (error.line(), error.column()));
+
+        let error = ParseError::at(input, &input[input.len()..], "missing");
+        assert_eq!((3, 4), (error.line(), error.column()));
+    }
+
+    #[test]
+    fn within_fragment() {
+        let input = "first\n  x,y\n  z\n";
+        let fragment = &input[8..];
+        let error = ParseError::at(fragment, &fragment[2..], "bad").within(input, fragment);
+        assert_eq!((2, 5), (error.line(), error.column()));
+
+        let error = ParseError::at(fragment, &fragment[6..], "bad").within(input, fragment);
+        assert_eq!((3, 3), (error.line(), error.column()));
+    }
+
+    #[test]
+    fn parse_at_reports_position() {
+        let input = "12,x4";
+        assert_eq!(Ok(12), parse_at::<u32>(input, &input[..2]));
+
+        let error = parse_at::<u32>(input, &input[3..]).unwrap_err().with_day(6);
+        assert_eq!("day 6, line 1, column 4: could not parse `x4`: invalid digit found in string", error.to_string());
+    }
+}
@@ -0,0 +1,213 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::Count;
+
+/// A graph to search: the states reachable in one step from any state, and
+/// what each step costs.
+pub trait Successors {
+    type State: Clone + Eq + Hash;
+    type Cost: Count;
+
+    fn successors(&self, state: &Self::State) -> impl IntoIterator<Item = (Self::State, Self::Cost)>;
+}
+
+/// A path found by a search, from the start to the goal inclusive, and its
+/// total cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// The cheapest path from `start` to any state satisfying `is_goal`.
+pub fn dijkstra<G: Successors>(
+    graph: &G,
+    start: G::State,
+    is_goal: impl FnMut(&G::State) -> bool,
+) -> Option<Path<G::State, G::Cost>> {
+    astar(graph, start, is_goal, |_| G::Cost::ZERO)
+}
+
+/// The cheapest path from `start` to any state satisfying `is_goal`, guided by
+/// `heuristic`.
+///
+/// The heuristic estimates the remaining cost from a state to the goal. It
+/// must never overestimate, or the path found may not be the cheapest.
+pub fn astar<G: Successors>(
+    graph: &G,
+    start: G::State,
+    mut is_goal: impl FnMut(&G::State) -> bool,
+    mut heuristic: impl FnMut(&G::State) -> G::Cost,
+) -> Option<Path<G::State, G::Cost>> {
+    // states are numbered in the order they are found, so the queue and the
+    // path back to the start only need to hold numbers
+    let mut indices = HashMap::new();
+    let mut nodes = vec![Node { state: start.clone(), previous: None, cost: G::Cost::ZERO }];
+    let mut queue = BinaryHeap::new();
+
+    queue.push(Reverse((heuristic(&start), G::Cost::ZERO, 0)));
+    indices.insert(start, 0);
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // a cheaper way here was found after this one was queued
+        if cost > nodes[index].cost {
+            continue;
+        }
+
+        let state = nodes[index].state.clone();
+        if is_goal(&state) {
+            let states = trace(&nodes, index);
+            return Some(Path { states, cost });
+        }
+
+        for (next, step) in graph.successors(&state) {
+            let next_cost = cost + step;
+            let next_index = match indices.entry(next) {
+                Entry::Vacant(entry) => {
+                    nodes.push(Node { state: entry.key().clone(), previous: Some(index), cost: next_cost });
+                    *entry.insert(nodes.len() - 1)
+                },
+                Entry::Occupied(entry) => {
+                    let node = &mut nodes[*entry.get()];
+                    if next_cost >= node.cost {
+                        continue;
+                    }
+                    node.previous = Some(index);
+                    node.cost = next_cost;
+                    *entry.get()
+                },
+            };
+            queue.push(Reverse((next_cost + heuristic(&nodes[next_index].state), next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+struct Node<S, C> {
+    state: S,
+    previous: Option<usize>,
+    cost: C,
+}
+
+// the states from the start to the node at `index`
+fn trace<S: Clone, C>(nodes: &[Node<S, C>], index: usize) -> Vec<S> {
+    let mut states = Vec::new();
+    let mut trace = Some(index);
+
+    while let Some(index) = trace {
+        states.push(nodes[index].state.clone());
+        trace = nodes[index].previous;
+    }
+
+    states.reverse();
+    states
+}
+
+/// The path from `start` to any state satisfying `is_goal` with the fewest
+/// steps, ignoring what each step costs. Its cost is the number of steps.
+pub fn bfs<G: Successors>(
+    graph: &G,
+    start: G::State,
+    mut is_goal: impl FnMut(&G::State) -> bool,
+) -> Option<Path<G::State, usize>> {
+    let mut previous = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(start.clone());
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            let states = reconstruct_path(&previous, state);
+            let cost = states.len() - 1;
+            return Some(Path { states, cost });
+        }
+
+        for (next, _) in graph.successors(&state) {
+            if next != start && !previous.contains_key(&next) {
+                previous.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Follow `previous`, which maps each state to the one it was reached from,
+/// back from `end` to a state with no predecessor. Returns the states from
+/// that start to `end` inclusive.
+pub fn reconstruct_path<S: Clone + Eq + Hash>(previous: &HashMap<S, S>, end: S) -> Vec<S> {
+    let mut path = vec![end];
+    while let Some(state) = previous.get(path.last().unwrap()) {
+        path.push(state.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a line of numbered states, where stepping forward by one costs 5 and
+    // jumping forward by three costs 12
+    struct Line;
+
+    impl Successors for Line {
+        type State = u32;
+        type Cost = u32;
+
+        fn successors(&self, &state: &u32) -> impl IntoIterator<Item = (u32, u32)> {
+            [(state + 1, 5), (state + 3, 12)]
+        }
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest() {
+        let path = dijkstra(&Line, 0, |&s| s == 6).unwrap();
+        assert_eq!(vec![0, 3, 6], path.states);
+        assert_eq!(24, path.cost);
+
+        let path = dijkstra(&Line, 0, |&s| s == 4).unwrap();
+        assert_eq!(vec![0, 1, 4], path.states);
+        assert_eq!(17, path.cost);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        // every step forward costs at least 4, though there are several
+        // equally cheap paths to choose from
+        let path = astar(&Line, 0, |&s| s == 10, |&s| 4 * 10_u32.saturating_sub(s)).unwrap();
+        assert_eq!(dijkstra(&Line, 0, |&s| s == 10).unwrap().cost, path.cost);
+        assert_eq!(41, path.cost);
+        assert_eq!(5, path.states.len());
+    }
+
+    #[test]
+    fn bfs_finds_fewest_steps() {
+        let path = bfs(&Line, 0, |&s| s == 7).unwrap();
+        assert_eq!(3, path.cost);
+        assert_eq!(Some(&7), path.states.last());
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        struct Dead;
+
+        impl Successors for Dead {
+            type State = u8;
+            type Cost = u8;
+
+            fn successors(&self, _: &u8) -> impl IntoIterator<Item = (u8, u8)> {
+                []
+            }
+        }
+
+        assert_eq!(None, dijkstra(&Dead, 0, |&s| s == 1));
+        assert_eq!(None, bfs(&Dead, 0, |&s| s == 1));
+    }
+}
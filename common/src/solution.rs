@@ -0,0 +1,104 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ParseError;
+
+/// A day's puzzle: how to parse its input, and how to answer both parts from
+/// the parsed input.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_one(input: &Self::Input) -> Answer;
+    fn part_two(input: &Self::Input) -> Answer;
+}
+
+/// The answer to one part of a puzzle.
+///
+/// Serializes as a plain JSON number, string, or `null`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    // the part has no answer, like day 25 part 2
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::None => write!(f, "none"),
+        }
+    }
+}
+
+macro_rules! answer_from_small {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i64)
+            }
+        }
+    )*};
+}
+
+// unsigned answers too large for an i64 are kept as text, which displays the
+// same way
+macro_rules! answer_from_large {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Number(n),
+                    Err(_) => Answer::Text(n.to_string()),
+                }
+            }
+        }
+    )*};
+}
+
+answer_from_small!(u8, u16, u32, i8, i16, i32, i64);
+answer_from_large!(u64, u128, usize, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(String::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_from_numbers() {
+        assert_eq!(Answer::Number(7), Answer::from(7_u32));
+        assert_eq!(Answer::Number(-3), Answer::from(-3_i32));
+        assert_eq!(Answer::Number(1_285_501_151_402_480), Answer::from(1_285_501_151_402_480_u64));
+    }
+
+    #[test]
+    fn answer_too_large_is_text() {
+        let n = u128::MAX;
+        assert_eq!(Answer::Text(n.to_string()), Answer::from(n));
+        assert_eq!(n.to_string(), Answer::from(n).to_string());
+    }
+
+    #[test]
+    fn answer_serializes_plainly() {
+        let answers = vec![Answer::from(42_u32), Answer::from("EFLFJGRF"), Answer::None];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(r#"[42,"EFLFJGRF",null]"#, json);
+        assert_eq!(answers, serde_json::from_str::<Vec<Answer>>(&json).unwrap());
+    }
+}
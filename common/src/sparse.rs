@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+/// A position in a `SparseGrid`, with `x` increasing to the right and `y`
+/// increasing downwards. Either may be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// An unbounded grid that only stores the cells it has been given. Every
+/// other cell holds the background value.
+///
+/// The bounding box of the stored cells is kept up to date as cells are
+/// inserted and removed, so rendering never needs to search for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    background: T,
+    // top left and bottom right corners, inclusive
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(background: T) -> Self {
+        Self { cells: HashMap::new(), background, bounds: None }
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// Change the value of every cell that is not stored.
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    /// The value at `p`, which is the background if it is not stored.
+    pub fn get(&self, p: Point) -> &T {
+        self.cells.get(&p).unwrap_or(&self.background)
+    }
+
+    /// Whether a value is stored at `p`, even one equal to the background.
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.bounds = Some(expand(self.bounds, p));
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        let removed = self.cells.remove(&p)?;
+
+        // only a cell on the edge of the bounding box can shrink it
+        if let Some((min, max)) = self.bounds {
+            if p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y {
+                self.bounds = self.cells.keys().fold(None, |bounds, &p| Some(expand(bounds, p)));
+            }
+        }
+
+        Some(removed)
+    }
+
+    /// The top left and bottom right corners of the stored cells, inclusive,
+    /// or `None` if nothing is stored.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// The number of stored cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Every stored cell, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    /// Every point in the bounding box, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let bounds = self.bounds;
+        bounds.into_iter().flat_map(|(min, max)| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+        })
+    }
+
+    /// Draw the bounding box as text, a row per line with no trailing newline.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let (min, max) = match self.bounds {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let mut result = String::new();
+        for y in min.y..=max.y {
+            if y > min.y {
+                result.push('\n');
+            }
+            for x in min.x..=max.x {
+                result.push(cell(self.get(Point::new(x, y))));
+            }
+        }
+        result
+    }
+}
+
+// the smallest bounding box holding both `bounds` and `p`
+fn expand(bounds: Option<(Point, Point)>, p: Point) -> (Point, Point) {
+    match bounds {
+        Some((min, max)) => (
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        ),
+        None => (p, p),
+    }
+}
+
+impl<T: Default> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::default();
+        for (p, value) in iter {
+            grid.insert(p, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_cells() {
+        let mut grid = SparseGrid::new(false);
+        assert_eq!(None, grid.bounds());
+
+        grid.insert(Point::new(2, 1), true);
+        grid.insert(Point::new(-3, 4), true);
+        assert_eq!(Some((Point::new(-3, 1), Point::new(2, 4))), grid.bounds());
+
+        grid.remove(Point::new(-3, 4));
+        assert_eq!(Some((Point::new(2, 1), Point::new(2, 1))), grid.bounds());
+
+        grid.remove(Point::new(2, 1));
+        assert_eq!(None, grid.bounds());
+    }
+
+    #[test]
+    fn background() {
+        let mut grid: SparseGrid<u8> = [(Point::new(0, 0), 1)].into_iter().collect();
+        assert_eq!(&0, grid.get(Point::new(-100, 100)));
+
+        grid.set_background(9);
+        assert_eq!(&9, grid.get(Point::new(-100, 100)));
+        assert_eq!(&1, grid.get(Point::new(0, 0)));
+    }
+
+    #[test]
+    fn render() {
+        let grid: SparseGrid<bool> = [(-1, -1), (1, 0)].into_iter()
+            .map(|(x, y)| (Point::new(x, y), true))
+            .collect();
+
+        assert_eq!("#..\n..#", grid.render(|&lit| if lit { '#' } else { '.' }));
+        assert_eq!(vec![Point::new(-1, -1), Point::new(0, -1)], grid.points().take(2).collect::<Vec<_>>());
+        assert_eq!("", SparseGrid::new(false).render(|_| '#'));
+    }
+}